@@ -0,0 +1,429 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Command line interface to hash scalars, encrypt and decrypt a
+//! [`PoseidonCipher`] and compute Merkle roots and branches.
+//!
+//! Scalars, points and ciphers are encoded as the hexadecimal string of their
+//! `to_bytes` representation, optionally `0x` prefixed. A leaf file contains
+//! one leaf hash per line, and ignores empty lines and lines starting with
+//! `#`.
+//!
+//! Every command prints plain text, or a JSON object if `--json` is passed.
+
+use std::{env, process};
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_jubjub::JubJubAffine;
+use dusk_poseidon::cipher::PoseidonCipher;
+use dusk_poseidon::sponge::{self, truncated};
+
+const USAGE: &str = "\
+Usage: dusk-poseidon [--json] <command> [args]
+
+Commands:
+  hash <scalar>...
+  hash-truncated <scalar>...
+  encrypt --secret <point> --nonce <scalar> <scalar>...
+  decrypt --secret <point> --nonce <scalar> <cipher>
+  tree root [--depth <depth>] <leaf-file>
+  tree branch --index <index> [--depth <depth>] <leaf-file>
+
+The depth of the tree defaults to 17.";
+
+/// Value of a field of the output of a command
+enum Value {
+    Hex(String),
+    #[cfg_attr(not(feature = "canon"), allow(dead_code))]
+    Number(u64),
+    List(Vec<Value>),
+    Object(Vec<(&'static str, Value)>),
+}
+
+impl Value {
+    fn json(&self) -> String {
+        match self {
+            Value::Hex(h) => format!("\"{}\"", h),
+            Value::Number(n) => format!("{}", n),
+            Value::List(l) => {
+                let l: Vec<String> = l.iter().map(Value::json).collect();
+                format!("[{}]", l.join(", "))
+            }
+            Value::Object(o) => {
+                let o: Vec<String> = o
+                    .iter()
+                    .map(|(k, v)| format!("\"{}\": {}", k, v.json()))
+                    .collect();
+                format!("{{{}}}", o.join(", "))
+            }
+        }
+    }
+
+    /// Plain representation, with the items of a list on separate lines
+    fn plain(&self) -> String {
+        match self {
+            Value::List(l) => {
+                let l: Vec<String> = l.iter().map(Value::inline).collect();
+                l.join("\n")
+            }
+            _ => self.inline(),
+        }
+    }
+
+    /// Plain representation in a single line
+    fn inline(&self) -> String {
+        match self {
+            Value::Hex(h) => h.clone(),
+            Value::Number(n) => format!("{}", n),
+            Value::List(l) => {
+                let l: Vec<String> = l.iter().map(Value::inline).collect();
+                l.join(" ")
+            }
+            Value::Object(o) => {
+                let o: Vec<String> =
+                    o.iter().map(|(_, v)| v.inline()).collect();
+                o.join(" ")
+            }
+        }
+    }
+}
+
+/// Output of a command
+///
+/// The plain output only prints the last field, that holds the result of the
+/// command, while the JSON output contains every field.
+struct Output(Vec<(&'static str, Value)>);
+
+impl Output {
+    fn print(self, json: bool) {
+        if json {
+            println!("{}", Value::Object(self.0).json());
+        } else if let Some((_, v)) = self.0.last() {
+            println!("{}", v.plain());
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> Value {
+    let h: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    Value::Hex(format!("0x{}", h))
+}
+
+fn scalars(scalars: &[BlsScalar]) -> Value {
+    Value::List(scalars.iter().map(|s| hex(&s.to_bytes())).collect())
+}
+
+fn decode<const N: usize>(s: &str) -> Result<[u8; N], String> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").unwrap_or(s);
+
+    // `from_str_radix` would accept a sign, so every digit is checked first
+    if s.len() != 2 * N || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(format!("Expected {} hexadecimal bytes, got '{}'", N, s));
+    }
+
+    let mut bytes = [0u8; N];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&s[2 * i..2 * i + 2], 16)
+            .map_err(|_| format!("Invalid hexadecimal string '{}'", s))?;
+    }
+
+    Ok(bytes)
+}
+
+fn scalar(s: &str) -> Result<BlsScalar, String> {
+    BlsScalar::from_bytes(&decode(s)?)
+        .map_err(|_| format!("Invalid scalar '{}'", s))
+}
+
+fn point(s: &str) -> Result<JubJubAffine, String> {
+    JubJubAffine::from_bytes(&decode(s)?)
+        .map_err(|_| format!("Invalid point '{}'", s))
+}
+
+fn cipher(s: &str) -> Result<PoseidonCipher, String> {
+    PoseidonCipher::from_bytes(&decode(s)?)
+        .map_err(|_| format!("Invalid cipher '{}'", s))
+}
+
+/// Arguments of a command, with its options removed
+struct Args {
+    options: Vec<(String, String)>,
+    positional: Vec<String>,
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = vec![];
+        let mut positional = vec![];
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(name) => {
+                    let value = args
+                        .next()
+                        .ok_or(format!("Missing value for '--{}'", name))?;
+
+                    options.push((String::from(name), value.clone()));
+                }
+                None => positional.push(arg.clone()),
+            }
+        }
+
+        Ok(Self {
+            options,
+            positional,
+        })
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn required(&self, name: &str) -> Result<&str, String> {
+        self.option(name)
+            .ok_or(format!("Missing required option '--{}'", name))
+    }
+
+    fn scalars(&self) -> Result<Vec<BlsScalar>, String> {
+        self.positional.iter().map(|s| scalar(s)).collect()
+    }
+}
+
+fn hash(args: &Args) -> Result<Output, String> {
+    let h = sponge::hash(&args.scalars()?);
+
+    Ok(Output(vec![("hash", hex(&h.to_bytes()))]))
+}
+
+fn hash_truncated(args: &Args) -> Result<Output, String> {
+    let h = truncated::hash(&args.scalars()?);
+
+    Ok(Output(vec![("hash", hex(&h.to_bytes()))]))
+}
+
+fn encrypt(args: &Args) -> Result<Output, String> {
+    let secret = point(args.required("secret")?)?;
+    let nonce = scalar(args.required("nonce")?)?;
+    let message = args.scalars()?;
+
+    if message.len() > PoseidonCipher::capacity() {
+        return Err(format!(
+            "The message can't have more than {} scalars",
+            PoseidonCipher::capacity()
+        ));
+    }
+
+    let cipher = PoseidonCipher::encrypt(&message, &secret, &nonce);
+
+    Ok(Output(vec![("cipher", hex(&cipher.to_bytes()))]))
+}
+
+fn decrypt(args: &Args) -> Result<Output, String> {
+    let secret = point(args.required("secret")?)?;
+    let nonce = scalar(args.required("nonce")?)?;
+
+    let cipher = match args.positional.as_slice() {
+        [c] => cipher(c)?,
+        _ => return Err(String::from("Expected a single cipher")),
+    };
+
+    let message = cipher
+        .decrypt(&secret, &nonce)
+        .map_err(|e| format!("{}", e))?;
+
+    Ok(Output(vec![("message", scalars(&message))]))
+}
+
+#[cfg(feature = "canon")]
+mod tree {
+    use super::{hex, scalar, scalars, Args, Output, Value};
+    use super::USAGE;
+
+    use std::fs;
+
+    use dusk_bls12_381::BlsScalar;
+    use dusk_bytes::Serializable;
+    use dusk_poseidon::tree::{PoseidonAnnotation, PoseidonTree};
+    use dusk_poseidon::vectors::VectorLeaf;
+
+    /// Depth of the tree if none is provided
+    const DEFAULT_DEPTH: usize = 17;
+
+    /// Call [`open`] for the runtime `$depth`
+    macro_rules! with_depth {
+        ($depth:expr, $leaves:ident, $index:ident, [$($d:literal),*]) => {
+            match $depth {
+                $($d => open::<$d>(&$leaves, $index),)*
+                d => Err(format!("Unsupported depth {}", d)),
+            }
+        };
+    }
+
+    fn leaves(args: &Args) -> Result<Vec<BlsScalar>, String> {
+        let path = match args.positional.as_slice() {
+            [path] => path,
+            _ => return Err(String::from("Expected a single leaf file")),
+        };
+
+        let leaves = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+
+        leaves
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(scalar)
+            .collect()
+    }
+
+    /// Compute the root of the tree of `leaves`, or the branch of the leaf
+    /// at `index`
+    fn open<const DEPTH: usize>(
+        leaves: &[BlsScalar],
+        index: Option<u64>,
+    ) -> Result<Output, String> {
+        // A branch only holds `DEPTH` levels, so the root of a larger tree
+        // would be silently computed from a truncated path
+        if leaves.len() as u128 > 1u128 << (2 * DEPTH) {
+            return Err(format!(
+                "A tree of depth {} holds at most 4^{} leaves, got {}",
+                DEPTH,
+                DEPTH,
+                leaves.len()
+            ));
+        }
+
+        let mut tree =
+            PoseidonTree::<VectorLeaf, PoseidonAnnotation, DEPTH>::new();
+
+        leaves.iter().try_for_each(|hash| {
+            tree.push(VectorLeaf::new(*hash))
+                .map(|_| ())
+                .map_err(|e| format!("{}", e))
+        })?;
+
+        let index = match index {
+            Some(index) => index,
+            None => {
+                let root = tree.root().map_err(|e| format!("{}", e))?;
+
+                return Ok(Output(vec![
+                    ("depth", Value::Number(DEPTH as u64)),
+                    ("leaves", Value::Number(leaves.len() as u64)),
+                    ("root", hex(&root.to_bytes())),
+                ]));
+            }
+        };
+
+        let branch = tree
+            .branch(index)
+            .map_err(|e| format!("{}", e))?
+            .ok_or(format!("No leaf at index {}", index))?;
+
+        let levels = branch
+            .as_ref()
+            .iter()
+            .map(|l| {
+                Value::Object(vec![
+                    ("offset", Value::Number(l.offset())),
+                    ("level", scalars(l.as_ref())),
+                ])
+            })
+            .collect();
+
+        Ok(Output(vec![
+            ("depth", Value::Number(DEPTH as u64)),
+            ("index", Value::Number(index)),
+            ("root", hex(&branch.root().to_bytes())),
+            ("levels", Value::List(levels)),
+        ]))
+    }
+
+    pub(super) fn run(args: &[String]) -> Result<Output, String> {
+        let (command, args) = match args.split_first() {
+            Some((command, args)) => (command.as_str(), Args::parse(args)?),
+            None => return Err(String::from(USAGE)),
+        };
+
+        let index = match command {
+            "root" => None,
+            "branch" => {
+                let index = args.required("index")?;
+                let index = index
+                    .parse()
+                    .map_err(|_| format!("Invalid index '{}'", index))?;
+
+                Some(index)
+            }
+            _ => return Err(String::from(USAGE)),
+        };
+
+        let depth = match args.option("depth") {
+            Some(d) => {
+                d.parse().map_err(|_| format!("Invalid depth '{}'", d))?
+            }
+            None => DEFAULT_DEPTH,
+        };
+
+        let leaves = leaves(&args)?;
+        if leaves.is_empty() {
+            return Err(String::from("The leaf file is empty"));
+        }
+
+        with_depth!(
+            depth,
+            leaves,
+            index,
+            [
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18,
+                19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+            ]
+        )
+    }
+}
+
+fn run(args: &[String]) -> Result<Output, String> {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => return Err(String::from(USAGE)),
+    };
+
+    match command {
+        "hash" => hash(&Args::parse(args)?),
+        "hash-truncated" => hash_truncated(&Args::parse(args)?),
+        "encrypt" => encrypt(&Args::parse(args)?),
+        "decrypt" => decrypt(&Args::parse(args)?),
+        #[cfg(feature = "canon")]
+        "tree" => tree::run(args),
+        #[cfg(not(feature = "canon"))]
+        "tree" => Err(String::from(
+            "The tree commands require the `canon` feature",
+        )),
+        _ => Err(String::from(USAGE)),
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let json = args.iter().any(|a| a == "--json");
+    args.retain(|a| a != "--json");
+
+    match run(&args) {
+        Ok(output) => output.print(json),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Print the known-answer vectors of [`dusk_poseidon::vectors`] as JSON
+
+use dusk_poseidon::{vectors, Error};
+
+fn main() -> Result<(), Error> {
+    println!("{}", vectors::json()?);
+
+    Ok(())
+}
@@ -9,6 +9,8 @@
 //! Encryption/decryption implementation with Dusk-Poseidon backend.
 //!
 //! This implementation is optimized for a message containing 2 scalars.
+//! [`Cipher`] takes the message capacity as a const generic, and
+//! [`PoseidonCipher`] is the cipher for a message of 2 scalars.
 //!
 //! ## Shared secret
 //!
@@ -18,6 +20,10 @@
 //!
 //! The suggestion is to use a Diffie-Hellman key exchange, as shown in the example. Check [dusk-jubjub](https://github.com/dusk-network/jubjub) for further reference.
 //!
+//! Instead of using the coordinates of the shared secret directly, a key can be
+//! derived from it with [`crate::kdf`] and used with
+//! [`PoseidonCipher::encrypt_with_key`].
+//!
 //! ## Example
 //!
 //! ```rust
@@ -84,6 +90,32 @@
 //! // Successful communication
 //! assert_eq!(decrypted_message, message);
 //! ```
+//!
+//! ## Sealed messages
+//!
+//! [`seal`] performs the key exchange of the example with a random ephemeral
+//! key, and returns a [`SealedMessage`] with everything the recipient needs to
+//! [`open`] it.
+//!
+//! ```rust
+//! use dusk_bls12_381::BlsScalar;
+//! use dusk_jubjub::{JubJubScalar, GENERATOR_EXTENDED};
+//! use dusk_poseidon::cipher;
+//! use rand_core::OsRng;
+//!
+//! let alice_secret = JubJubScalar::random(&mut OsRng);
+//! let alice_public = GENERATOR_EXTENDED * alice_secret;
+//!
+//! let a = BlsScalar::random(&mut OsRng);
+//! let b = BlsScalar::random(&mut OsRng);
+//! let message = [a, b];
+//!
+//! // The sealed message is safe to be broadcasted publicly
+//! let sealed = cipher::seal(&alice_public, &message, &mut OsRng);
+//! let opened = cipher::open(&alice_secret, &sealed).expect("Failed to open");
+//!
+//! assert_eq!(opened, message);
+//! ```
 
 use crate::Error;
 
@@ -92,68 +124,157 @@ use canonical_derive::Canon;
 
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::{DeserializableSlice, Error as BytesError, Serializable};
-use dusk_hades::{ScalarStrategy, Strategy};
+use dusk_hades::{ScalarStrategy, Strategy, WIDTH};
 use dusk_jubjub::JubJubAffine;
 
-const MESSAGE_CAPACITY: usize = 2;
-const CIPHER_SIZE: usize = MESSAGE_CAPACITY + 1;
-const CIPHER_BYTES_SIZE: usize = CIPHER_SIZE * BlsScalar::SIZE;
+#[cfg(feature = "alloc")]
+use dusk_plonk::prelude::{TurboComposer, Witness};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Default)]
-#[cfg_attr(feature = "canon", derive(Canon))]
-/// Encapsulates an encrypted data
-pub struct PoseidonCipher {
-    cipher: [BlsScalar; CIPHER_SIZE],
-}
+use core::fmt::Debug;
 
-impl Serializable<CIPHER_BYTES_SIZE> for PoseidonCipher {
-    type Error = BytesError;
-    /// Convert the instance to a bytes representation
-    fn to_bytes(&self) -> [u8; Self::SIZE] {
-        let mut bytes = [0u8; Self::SIZE];
+const MESSAGE_CAPACITY: usize = 2;
 
-        self.cipher.iter().enumerate().for_each(|(i, c)| {
-            let n = i * BlsScalar::SIZE;
-            bytes[n..n + BlsScalar::SIZE].copy_from_slice(&c.to_bytes());
-        });
+/// Cipher of a message of two scalars
+pub type PoseidonCipher = Cipher<MESSAGE_CAPACITY>;
 
-        bytes
-    }
+/// Message capacity of a [`Cipher`], as a type
+#[derive(Debug, Clone, Copy)]
+pub struct Capacity<const N: usize>;
 
-    /// Create an instance from a previous `PoseidonCipher::to_bytes` function
-    fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, Self::Error> {
-        let mut cipher: [BlsScalar; CIPHER_SIZE] =
-            [BlsScalar::zero(); CIPHER_SIZE];
+/// Bound of the scalars of a [`Cipher`], that must implement `Canon` with the
+/// `canon` feature
+#[cfg(feature = "canon")]
+pub trait MaybeCanon: canonical::Canon {}
+
+#[cfg(feature = "canon")]
+impl<T: canonical::Canon> MaybeCanon for T {}
+
+/// Bound of the scalars of a [`Cipher`], that must implement `Canon` with the
+/// `canon` feature
+#[cfg(not(feature = "canon"))]
+pub trait MaybeCanon {}
+
+#[cfg(not(feature = "canon"))]
+impl<T> MaybeCanon for T {}
+
+/// Arrays of the scalars and witnesses of a [`Cipher`] with a message capacity
+/// of `N` scalars, holding `N + 1` elements
+///
+/// Implemented for [`Capacity`] of 1 to 16 scalars.
+pub trait CipherCapacity {
+    /// Scalars of the cipher
+    type Scalars: Debug
+        + Copy
+        + Default
+        + Eq
+        + Ord
+        + AsRef<[BlsScalar]>
+        + AsMut<[BlsScalar]>
+        + MaybeCanon;
+
+    /// Witnesses of the cipher inside of a circuit
+    #[cfg(feature = "alloc")]
+    type Witnesses: Debug + Copy + AsRef<[Witness]> + AsMut<[Witness]>;
+
+    /// Cipher of zeroes
+    const ZERO: Self::Scalars;
+
+    /// Cipher of constant zero witnesses
+    #[cfg(feature = "alloc")]
+    const ZERO_WITNESSES: Self::Witnesses;
+}
 
-        for (i, scalar) in cipher.iter_mut().enumerate() {
-            let idx = i * BlsScalar::SIZE;
-            let len = idx + BlsScalar::SIZE;
-            *scalar = BlsScalar::from_slice(&bytes[idx..len])?;
-        }
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Default)]
+#[cfg_attr(feature = "canon", derive(Canon))]
+/// Encapsulates an encrypted message of up to `N` scalars
+///
+/// The cipher holds `N + 1` scalars, the last one being the authentication
+/// tag. The message is absorbed in chunks of `WIDTH - 1` scalars, so the
+/// cipher of [`PoseidonCipher`] is a single chunk.
+pub struct Cipher<const N: usize>
+where
+    Capacity<N>: CipherCapacity,
+{
+    cipher: <Capacity<N> as CipherCapacity>::Scalars,
+}
 
-        Ok(Self::new(cipher))
-    }
+/// Implement [`CipherCapacity`] and [`Serializable`] for the supported
+/// capacities
+macro_rules! capacity {
+    ($($n:literal),*) => {
+        $(
+            impl CipherCapacity for Capacity<$n> {
+                type Scalars = [BlsScalar; $n + 1];
+
+                #[cfg(feature = "alloc")]
+                type Witnesses = [Witness; $n + 1];
+
+                const ZERO: Self::Scalars = [BlsScalar::zero(); $n + 1];
+
+                #[cfg(feature = "alloc")]
+                const ZERO_WITNESSES: Self::Witnesses =
+                    [TurboComposer::constant_zero(); $n + 1];
+            }
+
+            impl Serializable<{ ($n + 1) * BlsScalar::SIZE }> for Cipher<$n> {
+                type Error = BytesError;
+
+                /// Convert the instance to a bytes representation
+                fn to_bytes(&self) -> [u8; Self::SIZE] {
+                    let mut bytes = [0u8; Self::SIZE];
+
+                    bytes
+                        .chunks_mut(BlsScalar::SIZE)
+                        .zip(self.cipher.iter())
+                        .for_each(|(b, c)| b.copy_from_slice(&c.to_bytes()));
+
+                    bytes
+                }
+
+                /// Create an instance from a previous `Cipher::to_bytes`
+                /// function
+                fn from_bytes(
+                    bytes: &[u8; Self::SIZE],
+                ) -> Result<Self, Self::Error> {
+                    let mut cipher = <Capacity<$n>>::ZERO;
+
+                    for (c, b) in
+                        cipher.iter_mut().zip(bytes.chunks(BlsScalar::SIZE))
+                    {
+                        *c = BlsScalar::from_slice(b)?;
+                    }
+
+                    Ok(Self::new(cipher))
+                }
+            }
+        )*
+    };
 }
 
-impl PoseidonCipher {
-    /// [`PoseidonCipher`] constructor
-    pub const fn new(cipher: [BlsScalar; CIPHER_SIZE]) -> Self {
+capacity!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+
+impl<const N: usize> Cipher<N>
+where
+    Capacity<N>: CipherCapacity,
+{
+    /// [`Cipher`] constructor
+    pub const fn new(cipher: <Capacity<N> as CipherCapacity>::Scalars) -> Self {
         Self { cipher }
     }
 
     /// Maximum number of scalars allowed per message
     pub const fn capacity() -> usize {
-        MESSAGE_CAPACITY
+        N
     }
 
     /// Number of scalars used in a cipher
     pub const fn cipher_size() -> usize {
-        CIPHER_SIZE
+        N + 1
     }
 
-    /// Number of bytes used by from/to bytes `PoseidonCipher` function
+    /// Number of bytes used by from/to bytes `Cipher` function
     pub const fn cipher_size_bytes() -> usize {
-        CIPHER_BYTES_SIZE
+        Self::cipher_size() * BlsScalar::SIZE
     }
 
     /// Returns the initial state of the encryption
@@ -161,90 +282,288 @@ impl PoseidonCipher {
         secret: &JubJubAffine,
         nonce: BlsScalar,
     ) -> [BlsScalar; dusk_hades::WIDTH] {
-        [
-            // Domain - Maximum plaintext length of the elements of Fq, as
-            // defined in the paper
-            BlsScalar::from_raw([0x100000000u64, 0, 0, 0]),
-            // The size of the message is constant because any absent input is
-            // replaced by zero
-            BlsScalar::from_raw([MESSAGE_CAPACITY as u64, 0, 0, 0]),
-            secret.get_x(),
-            secret.get_y(),
-            nonce,
-        ]
+        Self::initial_state_with_key(&[secret.get_x(), secret.get_y()], nonce)
+    }
+
+    /// Returns the initial state of the encryption for a key, such as the
+    /// one derived by [`crate::kdf::cipher_key`]
+    pub fn initial_state_with_key(
+        key: &[BlsScalar; 2],
+        nonce: BlsScalar,
+    ) -> [BlsScalar; dusk_hades::WIDTH] {
+        // The size of the message is constant because any absent input is
+        // replaced by zero
+        initial_state_with_len(key, nonce, N, 0)
     }
 
     /// Getter for the cipher
-    pub const fn cipher(&self) -> &[BlsScalar; CIPHER_SIZE] {
+    pub const fn cipher(&self) -> &<Capacity<N> as CipherCapacity>::Scalars {
         &self.cipher
     }
 
     /// Encrypt a slice of scalars into an internal cipher representation
     ///
-    /// The message size will be truncated to [`PoseidonCipher::capacity()`]
-    /// bits
+    /// The message size will be truncated to [`Cipher::capacity()`]
+    /// scalars
     pub fn encrypt(
         message: &[BlsScalar],
         secret: &JubJubAffine,
         nonce: &BlsScalar,
     ) -> Self {
-        let zero = BlsScalar::zero();
-        let mut strategy = ScalarStrategy::new();
-
-        let mut cipher = [zero; CIPHER_SIZE];
-        let mut state = PoseidonCipher::initial_state(secret, *nonce);
+        Self::encrypt_with_key(
+            message,
+            &[secret.get_x(), secret.get_y()],
+            nonce,
+        )
+    }
 
-        strategy.perm(&mut state);
+    /// Encrypt a slice of scalars with a key, such as the one derived by
+    /// [`crate::kdf::cipher_key`], instead of the raw shared secret
+    ///
+    /// The message size will be truncated to [`Cipher::capacity()`]
+    /// scalars
+    pub fn encrypt_with_key(
+        message: &[BlsScalar],
+        key: &[BlsScalar; 2],
+        nonce: &BlsScalar,
+    ) -> Self {
+        Self::encrypt_ad(message, &[], key, nonce)
+    }
 
-        (0..MESSAGE_CAPACITY).for_each(|i| {
-            state[i + 1] += if i < message.len() {
-                message[i]
-            } else {
-                BlsScalar::zero()
-            };
+    /// Perform the decrypt of a previously encrypted message.
+    ///
+    /// Will return an error if the decryption fails.
+    pub fn decrypt(
+        &self,
+        secret: &JubJubAffine,
+        nonce: &BlsScalar,
+    ) -> Result<[BlsScalar; N], Error> {
+        self.decrypt_with_key(&[secret.get_x(), secret.get_y()], nonce)
+    }
 
-            cipher[i] = state[i + 1];
-        });
+    /// Perform the decrypt of a message encrypted with
+    /// [`Cipher::encrypt_with_key`].
+    ///
+    /// Will return an error if the decryption fails.
+    pub fn decrypt_with_key(
+        &self,
+        key: &[BlsScalar; 2],
+        nonce: &BlsScalar,
+    ) -> Result<[BlsScalar; N], Error> {
+        self.decrypt_ad(&[], key, nonce)
+    }
 
-        strategy.perm(&mut state);
-        cipher[MESSAGE_CAPACITY] = state[1];
+    /// Encrypt a slice of scalars, authenticating the associated data `ad`
+    /// without encrypting it
+    ///
+    /// The associated data is absorbed before the message, so the tag of the
+    /// cipher covers both. With no associated data, the cipher is the same
+    /// as [`Cipher::encrypt`].
+    ///
+    /// The message size will be truncated to [`Cipher::capacity()`]
+    /// scalars
+    pub fn encrypt_with_ad(
+        message: &[BlsScalar],
+        ad: &[BlsScalar],
+        secret: &JubJubAffine,
+        nonce: &BlsScalar,
+    ) -> Self {
+        let key = [secret.get_x(), secret.get_y()];
 
-        PoseidonCipher::new(cipher)
+        Self::encrypt_ad(message, ad, &key, nonce)
     }
 
-    /// Perform the decrypt of a previously encrypted message.
+    /// Perform the decrypt of a message encrypted with
+    /// [`Cipher::encrypt_with_ad`].
     ///
-    /// Will return `None` if the decryption fails.
-    pub fn decrypt(
+    /// Will return an error if the cipher or the associated data was tampered
+    /// with.
+    pub fn decrypt_with_ad(
         &self,
+        ad: &[BlsScalar],
         secret: &JubJubAffine,
         nonce: &BlsScalar,
-    ) -> Result<[BlsScalar; MESSAGE_CAPACITY], Error> {
-        let zero = BlsScalar::zero();
-        let mut strategy = ScalarStrategy::new();
+    ) -> Result<[BlsScalar; N], Error> {
+        let key = [secret.get_x(), secret.get_y()];
 
-        let mut message = [zero; MESSAGE_CAPACITY];
-        let mut state = PoseidonCipher::initial_state(secret, *nonce);
+        self.decrypt_ad(ad, &key, nonce)
+    }
+
+    /// Encrypt the `message`, truncated or padded with zeroes to `N` scalars,
+    /// with the associated data `ad`
+    fn encrypt_ad(
+        message: &[BlsScalar],
+        ad: &[BlsScalar],
+        key: &[BlsScalar; 2],
+        nonce: &BlsScalar,
+    ) -> Self {
+        let mut padded = [BlsScalar::zero(); N];
+        padded
+            .iter_mut()
+            .zip(message.iter())
+            .for_each(|(p, m)| *p = *m);
 
-        strategy.perm(&mut state);
+        let mut cipher = <Capacity<N>>::ZERO;
+        encrypt_chunks(key, nonce, ad, &padded, cipher.as_mut());
 
-        (0..MESSAGE_CAPACITY).for_each(|i| {
-            message[i] = self.cipher[i] - state[i + 1];
-            state[i + 1] = self.cipher[i];
-        });
+        Self::new(cipher)
+    }
 
-        strategy.perm(&mut state);
+    /// Decrypt the cipher with the associated data `ad`
+    fn decrypt_ad(
+        &self,
+        ad: &[BlsScalar],
+        key: &[BlsScalar; 2],
+        nonce: &BlsScalar,
+    ) -> Result<[BlsScalar; N], Error> {
+        let mut message = [BlsScalar::zero(); N];
 
-        if self.cipher[MESSAGE_CAPACITY] != state[1] {
-            return Err(Error::CipherDecryptionFailed);
-        }
+        decrypt_chunks(key, nonce, ad, self.cipher.as_ref(), &mut message)?;
 
         Ok(message)
     }
 }
 
+/// Initial state of the encryption of a message of `len` scalars, with
+/// `ad_len` scalars of associated data
+fn initial_state_with_len(
+    key: &[BlsScalar; 2],
+    nonce: BlsScalar,
+    len: usize,
+    ad_len: usize,
+) -> [BlsScalar; WIDTH] {
+    [
+        // Domain - Maximum plaintext length of the elements of Fq, as
+        // defined in the paper
+        BlsScalar::from_raw([0x100000000u64, 0, 0, 0]),
+        BlsScalar::from_raw([len as u64, ad_len as u64, 0, 0]),
+        key[0],
+        key[1],
+        nonce,
+    ]
+}
+
+/// Absorb the associated data `ad` into the `state`, in chunks of `WIDTH - 1`
+/// scalars, each followed by a permutation
+///
+/// The length of `ad` is part of the initial state, so there is no padding.
+fn absorb_ad(
+    strategy: &mut ScalarStrategy,
+    state: &mut [BlsScalar; WIDTH],
+    ad: &[BlsScalar],
+) {
+    ad.chunks(WIDTH - 1).for_each(|chunk| {
+        state[1..]
+            .iter_mut()
+            .zip(chunk.iter())
+            .for_each(|(s, a)| *s += a);
+
+        strategy.perm(state);
+    });
+}
+
+/// Encrypt the `message` into the `cipher`, that must be one scalar longer,
+/// after absorbing the associated data `ad`
+///
+/// The message is absorbed in chunks of `WIDTH - 1` scalars, each followed by
+/// a permutation, and the last scalar of the cipher is the authentication tag.
+fn encrypt_chunks(
+    key: &[BlsScalar; 2],
+    nonce: &BlsScalar,
+    ad: &[BlsScalar],
+    message: &[BlsScalar],
+    cipher: &mut [BlsScalar],
+) {
+    let mut strategy = ScalarStrategy::new();
+    let mut state =
+        initial_state_with_len(key, *nonce, message.len(), ad.len());
+
+    strategy.perm(&mut state);
+    absorb_ad(&mut strategy, &mut state, ad);
+
+    message
+        .chunks(WIDTH - 1)
+        .zip(cipher.chunks_mut(WIDTH - 1))
+        .for_each(|(m, c)| {
+            state[1..]
+                .iter_mut()
+                .zip(m.iter().zip(c.iter_mut()))
+                .for_each(|(s, (m, c))| {
+                    *s += m;
+                    *c = *s;
+                });
+
+            strategy.perm(&mut state);
+        });
+
+    cipher[message.len()] = state[1];
+}
+
+/// Decrypt the `cipher` into the `message`, that must be one scalar shorter,
+/// as encrypted by [`encrypt_chunks`] with the same associated data `ad`
+fn decrypt_chunks(
+    key: &[BlsScalar; 2],
+    nonce: &BlsScalar,
+    ad: &[BlsScalar],
+    cipher: &[BlsScalar],
+    message: &mut [BlsScalar],
+) -> Result<(), Error> {
+    let mut strategy = ScalarStrategy::new();
+    let mut state =
+        initial_state_with_len(key, *nonce, message.len(), ad.len());
+
+    strategy.perm(&mut state);
+    absorb_ad(&mut strategy, &mut state, ad);
+
+    message
+        .chunks_mut(WIDTH - 1)
+        .zip(cipher.chunks(WIDTH - 1))
+        .for_each(|(m, c)| {
+            state[1..]
+                .iter_mut()
+                .zip(m.iter_mut().zip(c.iter()))
+                .for_each(|(s, (m, c))| {
+                    *m = c - *s;
+                    *s = *c;
+                });
+
+            strategy.perm(&mut state);
+        });
+
+    if cipher[message.len()] != state[1] {
+        return Err(Error::CipherDecryptionFailed);
+    }
+
+    Ok(())
+}
+
+mod seal;
+
+#[cfg(feature = "alloc")]
+mod bytes;
+#[cfg(feature = "alloc")]
+mod stream;
+#[cfg(feature = "alloc")]
+mod var;
 #[cfg(feature = "alloc")]
 mod zk;
 
+pub use seal::{open, seal, seal_with_ephemeral, SealedMessage};
+
+#[cfg(feature = "alloc")]
+pub use bytes::{
+    decrypt_bytes, decrypt_bytes_with_key, encrypt_bytes,
+    encrypt_bytes_with_key, BYTES_CIPHER_HEADER_SIZE, BYTES_CIPHER_VERSION,
+};
+#[cfg(feature = "alloc")]
+pub use stream::{StreamDecryptor, StreamEncryptor};
+#[cfg(feature = "alloc")]
+pub use var::PoseidonVarCipher;
+
 #[cfg(feature = "alloc")]
-pub use zk::{decrypt, encrypt};
+pub use zk::{
+    decrypt, decrypt_ad_gates, decrypt_gates, decrypt_var, decrypt_var_gates,
+    decrypt_var_with_key, decrypt_with_ad, decrypt_with_key, encrypt,
+    encrypt_ad_gates, encrypt_gates, encrypt_var, encrypt_var_gates,
+    encrypt_var_with_key, encrypt_with_ad, encrypt_with_key, seal_gadget,
+};
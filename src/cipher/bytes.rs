@@ -0,0 +1,147 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::PoseidonVarCipher;
+use crate::sponge::{pack, BYTES_PER_SCALAR};
+use crate::Error;
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_jubjub::JubJubAffine;
+
+/// Version tag of the serialized cipher of [`encrypt_bytes`]
+pub const BYTES_CIPHER_VERSION: u8 = 1;
+
+/// Number of bytes of the header of the serialized cipher of [`encrypt_bytes`]
+///
+/// The header is the [`BYTES_CIPHER_VERSION`] followed by the number of
+/// scalars of the cipher, as a little-endian `u32`.
+pub const BYTES_CIPHER_HEADER_SIZE: usize = 5;
+
+/// Encrypt an arbitrary byte string
+///
+/// The bytes are packed into scalars as in [`crate::sponge::hash_bytes`],
+/// prefixed by the number of bytes, and encrypted as a [`PoseidonVarCipher`].
+/// The returned cipher is serialized as a header of
+/// [`BYTES_CIPHER_HEADER_SIZE`] bytes, followed by the scalars of the cipher.
+pub fn encrypt_bytes(
+    bytes: &[u8],
+    secret: &JubJubAffine,
+    nonce: &BlsScalar,
+) -> Vec<u8> {
+    encrypt_bytes_with_key(bytes, &[secret.get_x(), secret.get_y()], nonce)
+}
+
+/// Encrypt an arbitrary byte string with a key, such as the one derived by
+/// [`crate::kdf::cipher_key`], instead of the raw shared secret
+pub fn encrypt_bytes_with_key(
+    bytes: &[u8],
+    key: &[BlsScalar; 2],
+    nonce: &BlsScalar,
+) -> Vec<u8> {
+    let message: Vec<BlsScalar> =
+        core::iter::once(BlsScalar::from(bytes.len() as u64))
+            .chain(bytes.chunks(BYTES_PER_SCALAR).map(pack))
+            .collect();
+
+    let cipher = PoseidonVarCipher::encrypt_with_key(&message, key, nonce);
+    let len = cipher.cipher().len() as u32;
+
+    let mut serialized = Vec::with_capacity(
+        BYTES_CIPHER_HEADER_SIZE + cipher.cipher().len() * BlsScalar::SIZE,
+    );
+
+    serialized.push(BYTES_CIPHER_VERSION);
+    serialized.extend_from_slice(&len.to_le_bytes());
+    serialized.extend_from_slice(&cipher.to_var_bytes());
+
+    serialized
+}
+
+/// Perform the decrypt of a byte string encrypted with [`encrypt_bytes`]
+///
+/// Will return an error if the serialized cipher is malformed, if the
+/// authentication tag doesn't match, or if the decrypted scalars are not the
+/// canonical packing of a byte string.
+pub fn decrypt_bytes(
+    cipher: &[u8],
+    secret: &JubJubAffine,
+    nonce: &BlsScalar,
+) -> Result<Vec<u8>, Error> {
+    decrypt_bytes_with_key(cipher, &[secret.get_x(), secret.get_y()], nonce)
+}
+
+/// Perform the decrypt of a byte string encrypted with
+/// [`encrypt_bytes_with_key`]
+pub fn decrypt_bytes_with_key(
+    cipher: &[u8],
+    key: &[BlsScalar; 2],
+    nonce: &BlsScalar,
+) -> Result<Vec<u8>, Error> {
+    if cipher.len() < BYTES_CIPHER_HEADER_SIZE
+        || cipher[0] != BYTES_CIPHER_VERSION
+    {
+        return Err(Error::CipherDecryptionFailed);
+    }
+
+    let (header, scalars) = cipher.split_at(BYTES_CIPHER_HEADER_SIZE);
+
+    let mut len = [0u8; 4];
+    len.copy_from_slice(&header[1..]);
+    let len = u32::from_le_bytes(len) as usize;
+
+    if scalars.len() != len * BlsScalar::SIZE {
+        return Err(Error::CipherDecryptionFailed);
+    }
+
+    let message = PoseidonVarCipher::from_slice(scalars)
+        .map_err(|_| Error::CipherDecryptionFailed)?
+        .decrypt_with_key(key, nonce)?;
+
+    unpack(&message).ok_or(Error::CipherDecryptionFailed)
+}
+
+/// Unpack the length-prefixed byte string of `message`, if every scalar is
+/// the canonical packing of its bytes
+fn unpack(message: &[BlsScalar]) -> Option<Vec<u8>> {
+    let (len, limbs) = message.split_first()?;
+
+    let len = len.to_bytes();
+    if len[8..].iter().any(|b| *b != 0) {
+        return None;
+    }
+
+    let mut len_bytes = [0u8; 8];
+    len_bytes.copy_from_slice(&len[..8]);
+    let len = u64::from_le_bytes(len_bytes) as usize;
+
+    // `len` is checked against the number of limbs before allocating, and
+    // only the last limb may be partially filled
+    let capacity = limbs.len() * BYTES_PER_SCALAR;
+    if len > capacity || len + BYTES_PER_SCALAR <= capacity {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(len);
+
+    for (i, limb) in limbs.iter().enumerate() {
+        let size = BYTES_PER_SCALAR.min(len - i * BYTES_PER_SCALAR);
+        let limb_bytes = limb.to_bytes();
+        let chunk = &limb_bytes[..size];
+
+        // Any byte beyond the chunk, including the padding of the last one,
+        // must be zero
+        if pack(chunk) != *limb {
+            return None;
+        }
+
+        bytes.extend_from_slice(chunk);
+    }
+
+    Some(bytes)
+}
@@ -0,0 +1,163 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::PoseidonCipher;
+use crate::Error;
+
+#[cfg(feature = "canon")]
+use canonical_derive::Canon;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::{DeserializableSlice, Error as BytesError, Serializable};
+use dusk_jubjub::{
+    dhke, JubJubAffine, JubJubExtended, JubJubScalar, GENERATOR_EXTENDED,
+};
+use rand_core::{CryptoRng, RngCore};
+
+const SEALED_MESSAGE_SIZE: usize =
+    JubJubAffine::SIZE + BlsScalar::SIZE + PoseidonCipher::SIZE;
+
+/// Message sealed to the public key of a recipient with [`seal`]
+///
+/// The message is encrypted with the shared secret of an ephemeral key and
+/// the public key of the recipient, and the ephemeral public key is
+/// authenticated as associated data of the cipher.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "canon", derive(Canon))]
+pub struct SealedMessage {
+    ephemeral: JubJubAffine,
+    nonce: BlsScalar,
+    cipher: PoseidonCipher,
+}
+
+impl SealedMessage {
+    /// [`SealedMessage`] constructor
+    pub const fn new(
+        ephemeral: JubJubAffine,
+        nonce: BlsScalar,
+        cipher: PoseidonCipher,
+    ) -> Self {
+        Self {
+            ephemeral,
+            nonce,
+            cipher,
+        }
+    }
+
+    /// Ephemeral public key of the sender
+    pub const fn ephemeral(&self) -> &JubJubAffine {
+        &self.ephemeral
+    }
+
+    /// Nonce of the encryption
+    pub const fn nonce(&self) -> &BlsScalar {
+        &self.nonce
+    }
+
+    /// Cipher of the message
+    pub const fn cipher(&self) -> &PoseidonCipher {
+        &self.cipher
+    }
+}
+
+impl Serializable<SEALED_MESSAGE_SIZE> for SealedMessage {
+    type Error = BytesError;
+
+    /// Convert the instance to a bytes representation, the concatenation of
+    /// the ephemeral public key, the nonce and the cipher
+    fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+
+        let (ephemeral, rest) = bytes.split_at_mut(JubJubAffine::SIZE);
+        let (nonce, cipher) = rest.split_at_mut(BlsScalar::SIZE);
+
+        ephemeral.copy_from_slice(&self.ephemeral.to_bytes());
+        nonce.copy_from_slice(&self.nonce.to_bytes());
+        cipher.copy_from_slice(&self.cipher.to_bytes());
+
+        bytes
+    }
+
+    /// Create an instance from a previous `SealedMessage::to_bytes` function
+    fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, Self::Error> {
+        let (ephemeral, rest) = bytes.split_at(JubJubAffine::SIZE);
+        let (nonce, cipher) = rest.split_at(BlsScalar::SIZE);
+
+        let ephemeral = JubJubAffine::from_slice(ephemeral)?;
+        let nonce = BlsScalar::from_slice(nonce)?;
+        let cipher = PoseidonCipher::from_slice(cipher)?;
+
+        Ok(Self::new(ephemeral, nonce, cipher))
+    }
+}
+
+/// Seal the `message` to the public key of the `recipient`
+///
+/// A random ephemeral key and nonce are generated, and the message is
+/// encrypted with the Diffie-Hellman shared secret of the ephemeral key and
+/// the recipient. The message size will be truncated to
+/// [`PoseidonCipher::capacity()`].
+pub fn seal<R>(
+    recipient: &JubJubExtended,
+    message: &[BlsScalar],
+    rng: &mut R,
+) -> SealedMessage
+where
+    R: RngCore + CryptoRng,
+{
+    let ephemeral_secret = JubJubScalar::random(rng);
+    let nonce = BlsScalar::random(rng);
+
+    seal_with_ephemeral(recipient, message, &ephemeral_secret, &nonce)
+}
+
+/// Seal the `message` to the public key of the `recipient` with the provided
+/// ephemeral secret and nonce
+///
+/// The ephemeral secret must never be reused, so [`seal`] should be preferred
+/// outside of tests and circuits.
+pub fn seal_with_ephemeral(
+    recipient: &JubJubExtended,
+    message: &[BlsScalar],
+    ephemeral_secret: &JubJubScalar,
+    nonce: &BlsScalar,
+) -> SealedMessage {
+    let ephemeral: JubJubAffine =
+        (GENERATOR_EXTENDED * ephemeral_secret).into();
+    let shared_secret = dhke(ephemeral_secret, recipient);
+
+    let ad = [ephemeral.get_x(), ephemeral.get_y()];
+    let cipher =
+        PoseidonCipher::encrypt_with_ad(message, &ad, &shared_secret, nonce);
+
+    SealedMessage::new(ephemeral, *nonce, cipher)
+}
+
+/// Open a message sealed to the public key of `recipient_secret`
+///
+/// Will return an error if the message wasn't sealed to the recipient, or if
+/// it was tampered with.
+///
+/// The ephemeral public key must be of prime order. Otherwise, a small-order
+/// component would make the shared secret depend on the recipient secret
+/// modulo the cofactor, and the outcome of the decryption would leak it.
+pub fn open(
+    recipient_secret: &JubJubScalar,
+    sealed: &SealedMessage,
+) -> Result<[BlsScalar; PoseidonCipher::capacity()], Error> {
+    let ephemeral = JubJubExtended::from(sealed.ephemeral);
+    if !bool::from(ephemeral.is_prime_order()) {
+        return Err(Error::CipherDecryptionFailed);
+    }
+
+    let shared_secret = dhke(recipient_secret, &ephemeral);
+
+    let ad = [sealed.ephemeral.get_x(), sealed.ephemeral.get_y()];
+
+    sealed
+        .cipher
+        .decrypt_with_ad(&ad, &shared_secret, &sealed.nonce)
+}
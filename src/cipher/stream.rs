@@ -0,0 +1,196 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::{Capacity, Cipher, CipherCapacity};
+use crate::Error;
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::JubJubAffine;
+
+/// Associated data of the chunk `index` of a stream
+///
+/// The final chunk is flagged with a one, so a stream can't be truncated
+/// without failing the decryption of its last chunk.
+fn chunk_ad(index: u64, last: bool) -> [BlsScalar; 2] {
+    let last = if last {
+        BlsScalar::one()
+    } else {
+        BlsScalar::zero()
+    };
+
+    [BlsScalar::from(index), last]
+}
+
+/// Streaming encryption of a message of any length, in chunks of `N` scalars
+///
+/// Every chunk is a [`Cipher`] with its index and a final-chunk flag as
+/// associated data, so the chunks can't be reordered, dropped or truncated
+/// without failing the decryption. A chunk is emitted as soon as it is full,
+/// and [`StreamEncryptor::finalize`] emits the final chunk, padded with a `1`
+/// followed by zeroes as in [`crate::sponge::hash`].
+///
+/// The nonce must not be reused for another stream with the same secret.
+#[derive(Debug, Clone)]
+pub struct StreamEncryptor<const N: usize>
+where
+    Capacity<N>: CipherCapacity,
+{
+    key: [BlsScalar; 2],
+    nonce: BlsScalar,
+    index: u64,
+    chunk: [BlsScalar; N],
+    pos: usize,
+}
+
+impl<const N: usize> StreamEncryptor<N>
+where
+    Capacity<N>: CipherCapacity,
+{
+    /// Create a new encryptor for the shared secret and nonce
+    pub fn new(secret: &JubJubAffine, nonce: BlsScalar) -> Self {
+        Self::with_key(&[secret.get_x(), secret.get_y()], nonce)
+    }
+
+    /// Create a new encryptor for a key, such as the one derived by
+    /// [`crate::kdf::cipher_key`], instead of the raw shared secret
+    pub fn with_key(key: &[BlsScalar; 2], nonce: BlsScalar) -> Self {
+        Self {
+            key: *key,
+            nonce,
+            index: 0,
+            chunk: [BlsScalar::zero(); N],
+            pos: 0,
+        }
+    }
+
+    /// Index of the next chunk to be emitted
+    pub const fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Encrypt a single scalar of the message, returning the cipher of the
+    /// chunk if it is full
+    pub fn encrypt(&mut self, message: &BlsScalar) -> Option<Cipher<N>> {
+        self.chunk[self.pos] = *message;
+        self.pos += 1;
+
+        if self.pos < N {
+            return None;
+        }
+
+        let cipher = self.encrypt_chunk(false);
+
+        self.chunk = [BlsScalar::zero(); N];
+        self.pos = 0;
+        self.index += 1;
+
+        Some(cipher)
+    }
+
+    /// Encrypt a slice of scalars of the message, returning the ciphers of
+    /// the chunks filled by it
+    pub fn encrypt_slice(&mut self, message: &[BlsScalar]) -> Vec<Cipher<N>> {
+        message.iter().filter_map(|m| self.encrypt(m)).collect()
+    }
+
+    /// Pad the remaining scalars of the message and return the cipher of the
+    /// final chunk
+    pub fn finalize(mut self) -> Cipher<N> {
+        // There is always room for the padding, since full chunks are
+        // emitted right away
+        self.chunk[self.pos] = BlsScalar::one();
+
+        self.encrypt_chunk(true)
+    }
+
+    fn encrypt_chunk(&self, last: bool) -> Cipher<N> {
+        let ad = chunk_ad(self.index, last);
+
+        Cipher::encrypt_ad(&self.chunk, &ad, &self.key, &self.nonce)
+    }
+}
+
+/// Streaming decryption of the chunks emitted by a [`StreamEncryptor`]
+///
+/// The chunks must be provided in order, and the last one to
+/// [`StreamDecryptor::finalize`]. A chunk that fails the authentication is
+/// reported with [`Error::StreamChunkFailed`] and its index, and the decryptor
+/// is left unchanged.
+#[derive(Debug, Clone)]
+pub struct StreamDecryptor<const N: usize>
+where
+    Capacity<N>: CipherCapacity,
+{
+    key: [BlsScalar; 2],
+    nonce: BlsScalar,
+    index: u64,
+}
+
+impl<const N: usize> StreamDecryptor<N>
+where
+    Capacity<N>: CipherCapacity,
+{
+    /// Create a new decryptor for the shared secret and nonce
+    pub fn new(secret: &JubJubAffine, nonce: BlsScalar) -> Self {
+        Self::with_key(&[secret.get_x(), secret.get_y()], nonce)
+    }
+
+    /// Create a new decryptor for a key used with
+    /// [`StreamEncryptor::with_key`]
+    pub fn with_key(key: &[BlsScalar; 2], nonce: BlsScalar) -> Self {
+        Self {
+            key: *key,
+            nonce,
+            index: 0,
+        }
+    }
+
+    /// Index of the next chunk to be decrypted
+    pub const fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Decrypt the next chunk of the stream, that must not be the final one
+    pub fn decrypt(
+        &mut self,
+        cipher: &Cipher<N>,
+    ) -> Result<[BlsScalar; N], Error> {
+        let message = self.decrypt_chunk(cipher, false)?;
+        self.index += 1;
+
+        Ok(message)
+    }
+
+    /// Decrypt the final chunk of the stream, returning the remaining scalars
+    /// of the message without the padding
+    pub fn finalize(self, cipher: &Cipher<N>) -> Result<Vec<BlsScalar>, Error> {
+        let message = self.decrypt_chunk(cipher, true)?;
+
+        // The padding is authenticated, so it is only malformed if the chunk
+        // wasn't emitted by a `StreamEncryptor`
+        let len = message
+            .iter()
+            .rposition(|m| m != &BlsScalar::zero())
+            .filter(|&i| message[i] == BlsScalar::one())
+            .ok_or(Error::StreamChunkFailed(self.index))?;
+
+        Ok(message[..len].to_vec())
+    }
+
+    fn decrypt_chunk(
+        &self,
+        cipher: &Cipher<N>,
+        last: bool,
+    ) -> Result<[BlsScalar; N], Error> {
+        let ad = chunk_ad(self.index, last);
+
+        cipher
+            .decrypt_ad(&ad, &self.key, &self.nonce)
+            .map_err(|_| Error::StreamChunkFailed(self.index))
+    }
+}
@@ -0,0 +1,145 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::{decrypt_chunks, encrypt_chunks};
+use crate::Error;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "canon")]
+use canonical_derive::Canon;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::{DeserializableSlice, Error as BytesError, Serializable};
+use dusk_jubjub::JubJubAffine;
+
+/// Encapsulates an encrypted message of any length
+///
+/// The message is absorbed in chunks of `WIDTH - 1` scalars, and the cipher
+/// holds one scalar more than the message, for the authentication tag. The
+/// length of the message is part of the initial state, so a cipher can't be
+/// truncated or extended without failing the decryption.
+///
+/// The cipher of a message of [`super::PoseidonCipher::capacity`] scalars is
+/// the same as the one of [`super::PoseidonCipher`].
+#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Default)]
+#[cfg_attr(feature = "canon", derive(Canon))]
+pub struct PoseidonVarCipher {
+    cipher: Vec<BlsScalar>,
+}
+
+impl PoseidonVarCipher {
+    /// [`PoseidonVarCipher`] constructor
+    ///
+    /// The last scalar of `cipher` is the authentication tag, so it can't be
+    /// empty.
+    pub fn new(cipher: Vec<BlsScalar>) -> Self {
+        Self { cipher }
+    }
+
+    /// Number of scalars used in the cipher of a message of `len` scalars
+    pub const fn cipher_size(len: usize) -> usize {
+        len + 1
+    }
+
+    /// Number of scalars of the encrypted message
+    pub fn message_len(&self) -> usize {
+        self.cipher.len().saturating_sub(1)
+    }
+
+    /// Getter for the cipher
+    pub fn cipher(&self) -> &[BlsScalar] {
+        &self.cipher
+    }
+
+    /// Encrypt a slice of scalars of any length
+    pub fn encrypt(
+        message: &[BlsScalar],
+        secret: &JubJubAffine,
+        nonce: &BlsScalar,
+    ) -> Self {
+        Self::encrypt_with_key(
+            message,
+            &[secret.get_x(), secret.get_y()],
+            nonce,
+        )
+    }
+
+    /// Encrypt a slice of scalars of any length with a key, such as the one
+    /// derived by [`crate::kdf::cipher_key`], instead of the raw shared secret
+    pub fn encrypt_with_key(
+        message: &[BlsScalar],
+        key: &[BlsScalar; 2],
+        nonce: &BlsScalar,
+    ) -> Self {
+        let mut cipher =
+            vec![BlsScalar::zero(); Self::cipher_size(message.len())];
+
+        encrypt_chunks(key, nonce, &[], message, &mut cipher);
+
+        Self::new(cipher)
+    }
+
+    /// Perform the decrypt of a previously encrypted message.
+    ///
+    /// Will return an error if the authentication tag doesn't match.
+    pub fn decrypt(
+        &self,
+        secret: &JubJubAffine,
+        nonce: &BlsScalar,
+    ) -> Result<Vec<BlsScalar>, Error> {
+        self.decrypt_with_key(&[secret.get_x(), secret.get_y()], nonce)
+    }
+
+    /// Perform the decrypt of a message encrypted with
+    /// [`PoseidonVarCipher::encrypt_with_key`].
+    ///
+    /// Will return an error if the authentication tag doesn't match.
+    pub fn decrypt_with_key(
+        &self,
+        key: &[BlsScalar; 2],
+        nonce: &BlsScalar,
+    ) -> Result<Vec<BlsScalar>, Error> {
+        if self.cipher.is_empty() {
+            return Err(Error::CipherDecryptionFailed);
+        }
+
+        let mut message = vec![BlsScalar::zero(); self.message_len()];
+
+        decrypt_chunks(key, nonce, &[], &self.cipher, &mut message)?;
+
+        Ok(message)
+    }
+
+    /// Convert the instance to a bytes representation, the concatenation of
+    /// the bytes of the scalars of the cipher
+    pub fn to_var_bytes(&self) -> Vec<u8> {
+        self.cipher.iter().flat_map(|c| c.to_bytes()).collect()
+    }
+
+    /// Create an instance from a previous
+    /// [`PoseidonVarCipher::to_var_bytes`] representation
+    ///
+    /// The length of the message is implied by the length of `bytes`, that
+    /// must be a non-zero multiple of the size of a scalar.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, BytesError> {
+        if bytes.is_empty() || bytes.len() % BlsScalar::SIZE != 0 {
+            let len = bytes.len() / BlsScalar::SIZE + 1;
+
+            return Err(BytesError::BadLength {
+                found: bytes.len(),
+                expected: len * BlsScalar::SIZE,
+            });
+        }
+
+        bytes
+            .chunks(BlsScalar::SIZE)
+            .map(BlsScalar::from_slice)
+            .collect::<Result<Vec<BlsScalar>, BytesError>>()
+            .map(Self::new)
+    }
+}
@@ -4,12 +4,20 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use crate::cipher::PoseidonCipher;
-use dusk_hades::GadgetStrategy;
+use crate::cipher::{Capacity, Cipher, CipherCapacity, PoseidonCipher};
+use crate::sponge::PERMUTATION_GATES;
+use dusk_hades::{GadgetStrategy, WIDTH};
 
+use alloc::vec;
+use alloc::vec::Vec;
+
+use dusk_jubjub::GENERATOR_EXTENDED;
 use dusk_plonk::prelude::*;
 
-impl PoseidonCipher {
+impl<const N: usize> Cipher<N>
+where
+    Capacity<N>: CipherCapacity,
+{
     /// Returns the initial state of the encryption within a composer circuit
     pub fn initial_state_circuit(
         composer: &mut TurboComposer,
@@ -17,92 +25,436 @@ impl PoseidonCipher {
         ks1: Witness,
         nonce: Witness,
     ) -> [Witness; dusk_hades::WIDTH] {
-        let domain = BlsScalar::from_raw([0x100000000u64, 0, 0, 0]);
-        let domain = composer.append_constant(domain);
+        initial_state_with_len(composer, &[ks0, ks1], nonce, N, 0)
+    }
+}
 
-        let length =
-            BlsScalar::from_raw([PoseidonCipher::capacity() as u64, 0, 0, 0]);
-        let length = composer.append_constant(length);
+/// Number of gates appended to the circuit by [`encrypt`] and
+/// [`encrypt_with_key`] for a capacity of `N` scalars
+///
+/// The same as [`encrypt_var_gates`] for a message of `N` scalars, since
+/// any absent input is replaced by zero.
+pub const fn encrypt_gates<const N: usize>() -> usize {
+    encrypt_var_gates(N)
+}
 
-        [domain, length, ks0, ks1, nonce]
-    }
+/// Number of gates appended to the circuit by [`decrypt`] and
+/// [`decrypt_with_key`] for a capacity of `N` scalars
+///
+/// The same as [`encrypt_gates`], with an additional gate to check the last
+/// element of the cipher.
+pub const fn decrypt_gates<const N: usize>() -> usize {
+    encrypt_gates::<N>() + 1
 }
 
 /// Given a shared secret calculated using any key protocol compatible with bls and jubjub, perform
 /// the encryption of the message.
 ///
 /// The returned set of variables is the cipher text
-pub fn encrypt(
+pub fn encrypt<const N: usize>(
     composer: &mut TurboComposer,
     shared_secret: &WitnessPoint,
     nonce: Witness,
     message: &[Witness],
-) -> [Witness; PoseidonCipher::cipher_size()] {
-    let zero = TurboComposer::constant_zero();
+) -> <Capacity<N> as CipherCapacity>::Witnesses
+where
+    Capacity<N>: CipherCapacity,
+{
+    let key = [*shared_secret.x(), *shared_secret.y()];
+
+    encrypt_with_key::<N>(composer, &key, nonce, message)
+}
+
+/// Mirror the implementation of [`Cipher::encrypt_with_key`] inside of a
+/// PLONK circuit.
+///
+/// The message size will be truncated to [`Cipher::capacity()`]. The
+/// returned set of variables is the cipher text
+pub fn encrypt_with_key<const N: usize>(
+    composer: &mut TurboComposer,
+    key: &[Witness; 2],
+    nonce: Witness,
+    message: &[Witness],
+) -> <Capacity<N> as CipherCapacity>::Witnesses
+where
+    Capacity<N>: CipherCapacity,
+{
+    encrypt_padded::<N>(composer, key, nonce, &[], message)
+}
+
+/// Given a shared secret calculated using any key protocol compatible with bls and jubjub, perform
+/// the decryption of the cipher.
+///
+/// The returned set of variables is the original message
+pub fn decrypt<const N: usize>(
+    composer: &mut TurboComposer,
+    shared_secret: &WitnessPoint,
+    nonce: Witness,
+    cipher: &[Witness],
+) -> [Witness; N]
+where
+    Capacity<N>: CipherCapacity,
+{
+    let key = [*shared_secret.x(), *shared_secret.y()];
 
-    let ks0 = *shared_secret.x();
-    let ks1 = *shared_secret.y();
+    decrypt_with_key::<N>(composer, &key, nonce, cipher)
+}
 
-    let mut cipher = [zero; PoseidonCipher::cipher_size()];
+/// Mirror the implementation of [`Cipher::decrypt_with_key`] inside of a
+/// PLONK circuit.
+///
+/// The `cipher` is expected to hold [`Cipher::cipher_size()`] variables. The
+/// returned set of variables is the original message
+pub fn decrypt_with_key<const N: usize>(
+    composer: &mut TurboComposer,
+    key: &[Witness; 2],
+    nonce: Witness,
+    cipher: &[Witness],
+) -> [Witness; N]
+where
+    Capacity<N>: CipherCapacity,
+{
+    let mut message = [TurboComposer::constant_zero(); N];
 
-    let mut state =
-        PoseidonCipher::initial_state_circuit(composer, ks0, ks1, nonce);
+    decrypt_chunks(composer, key, nonce, &[], &cipher[..N + 1], &mut message);
 
-    GadgetStrategy::gadget(composer, &mut state);
+    message
+}
+
+/// Number of gates appended to the circuit by [`encrypt_with_ad`] for a
+/// capacity of `N` scalars and `ad` scalars of associated data
+///
+/// The same as [`encrypt_gates`], with an addition gate per scalar of the
+/// associated data, and a permutation per chunk of `WIDTH - 1` scalars.
+pub const fn encrypt_ad_gates<const N: usize>(ad: usize) -> usize {
+    let chunks = (ad + WIDTH - 2) / (WIDTH - 1);
 
-    (0..PoseidonCipher::capacity()).for_each(|i| {
-        let x = if i < message.len() { message[i] } else { zero };
+    encrypt_gates::<N>() + ad + chunks * PERMUTATION_GATES
+}
+
+/// Number of gates appended to the circuit by [`decrypt_with_ad`] for a
+/// capacity of `N` scalars and `ad` scalars of associated data
+///
+/// The same as [`encrypt_ad_gates`], with an additional gate to check the
+/// authentication tag.
+pub const fn decrypt_ad_gates<const N: usize>(ad: usize) -> usize {
+    encrypt_ad_gates::<N>(ad) + 1
+}
+
+/// Mirror the implementation of [`Cipher::encrypt_with_ad`] inside of a
+/// PLONK circuit.
+///
+/// The circuit is defined by the length of the associated data `ad`. The
+/// returned set of variables is the cipher text
+pub fn encrypt_with_ad<const N: usize>(
+    composer: &mut TurboComposer,
+    shared_secret: &WitnessPoint,
+    nonce: Witness,
+    ad: &[Witness],
+    message: &[Witness],
+) -> <Capacity<N> as CipherCapacity>::Witnesses
+where
+    Capacity<N>: CipherCapacity,
+{
+    let key = [*shared_secret.x(), *shared_secret.y()];
+
+    encrypt_padded::<N>(composer, &key, nonce, ad, message)
+}
+
+/// Mirror the implementation of [`Cipher::decrypt_with_ad`] inside of a
+/// PLONK circuit.
+///
+/// The circuit is not satisfied if the cipher or the associated data `ad`
+/// was tampered with. The returned set of variables is the original message
+pub fn decrypt_with_ad<const N: usize>(
+    composer: &mut TurboComposer,
+    shared_secret: &WitnessPoint,
+    nonce: Witness,
+    ad: &[Witness],
+    cipher: &[Witness],
+) -> [Witness; N]
+where
+    Capacity<N>: CipherCapacity,
+{
+    let key = [*shared_secret.x(), *shared_secret.y()];
+    let mut message = [TurboComposer::constant_zero(); N];
 
-        let constraint =
-            Constraint::new().left(1).a(state[i + 1]).right(1).b(x);
+    decrypt_chunks(composer, &key, nonce, ad, &cipher[..N + 1], &mut message);
 
-        state[i + 1] = composer.gate_add(constraint);
+    message
+}
+
+/// Mirror the implementation of [`seal_with_ephemeral`] inside of a PLONK
+/// circuit.
+///
+/// The ephemeral public key is computed from `ephemeral_secret`, and the
+/// message is encrypted with its shared secret with the `recipient`. The
+/// returned variables are the ephemeral public key and the cipher, to be
+/// constrained against the [`SealedMessage`] that proves correct sealing to
+/// the recipient.
+///
+/// The gadget doesn't constrain `recipient` to the prime-order subgroup, so
+/// the caller is responsible for it, i.e. by constraining it to a known
+/// public key.
+///
+/// [`seal_with_ephemeral`]: crate::cipher::seal_with_ephemeral
+/// [`SealedMessage`]: crate::cipher::SealedMessage
+pub fn seal_gadget(
+    composer: &mut TurboComposer,
+    recipient: WitnessPoint,
+    ephemeral_secret: Witness,
+    nonce: Witness,
+    message: &[Witness],
+) -> (WitnessPoint, [Witness; PoseidonCipher::cipher_size()]) {
+    let ephemeral =
+        composer.component_mul_generator(ephemeral_secret, GENERATOR_EXTENDED);
+    let shared_secret =
+        composer.component_mul_point(ephemeral_secret, recipient);
+
+    let ad = [*ephemeral.x(), *ephemeral.y()];
+    let cipher = encrypt_with_ad::<{ PoseidonCipher::capacity() }>(
+        composer,
+        &shared_secret,
+        nonce,
+        &ad,
+        message,
+    );
+
+    (ephemeral, cipher)
+}
+
+/// Append the encryption of the `message`, truncated or padded with zeroes to
+/// `N` variables
+fn encrypt_padded<const N: usize>(
+    composer: &mut TurboComposer,
+    key: &[Witness; 2],
+    nonce: Witness,
+    ad: &[Witness],
+    message: &[Witness],
+) -> <Capacity<N> as CipherCapacity>::Witnesses
+where
+    Capacity<N>: CipherCapacity,
+{
+    let mut padded = [TurboComposer::constant_zero(); N];
+    padded
+        .iter_mut()
+        .zip(message.iter())
+        .for_each(|(p, m)| *p = *m);
 
-        cipher[i] = state[i + 1];
+    let mut cipher = <Capacity<N>>::ZERO_WITNESSES;
+    encrypt_chunks(composer, key, nonce, ad, &padded, cipher.as_mut());
+
+    cipher
+}
+
+/// Append the initial state of the encryption of a message of `len` scalars,
+/// with `ad_len` scalars of associated data
+fn initial_state_with_len(
+    composer: &mut TurboComposer,
+    key: &[Witness; 2],
+    nonce: Witness,
+    len: usize,
+    ad_len: usize,
+) -> [Witness; WIDTH] {
+    let domain = BlsScalar::from_raw([0x100000000u64, 0, 0, 0]);
+    let domain = composer.append_constant(domain);
+
+    let length = BlsScalar::from_raw([len as u64, ad_len as u64, 0, 0]);
+    let length = composer.append_constant(length);
+
+    [domain, length, key[0], key[1], nonce]
+}
+
+/// Append the absorption of the associated data `ad` into the `state`, in
+/// chunks of `WIDTH - 1` variables, each followed by a permutation
+fn absorb_ad(
+    composer: &mut TurboComposer,
+    state: &mut [Witness; WIDTH],
+    ad: &[Witness],
+) {
+    ad.chunks(WIDTH - 1).for_each(|chunk| {
+        state[1..].iter_mut().zip(chunk.iter()).for_each(|(s, a)| {
+            let constraint = Constraint::new().left(1).a(*s).right(1).b(*a);
+
+            *s = composer.gate_add(constraint);
+        });
+
+        GadgetStrategy::gadget(composer, state);
     });
+}
 
-    GadgetStrategy::gadget(composer, &mut state);
-    cipher[PoseidonCipher::capacity()] = state[1];
+/// Number of gates appended to the circuit by [`encrypt_var`] and
+/// [`encrypt_var_with_key`] for a message of `n` scalars
+///
+/// The initial state takes two constants, and every message takes an addition
+/// gate. Every chunk of `WIDTH - 1` scalars is followed by a permutation.
+pub const fn encrypt_var_gates(n: usize) -> usize {
+    let chunks = (n + WIDTH - 2) / (WIDTH - 1);
+
+    2 + n + (chunks + 1) * PERMUTATION_GATES
+}
+
+/// Number of gates appended to the circuit by [`decrypt_var`] and
+/// [`decrypt_var_with_key`] for a message of `n` scalars
+///
+/// The same as [`encrypt_var_gates`], with an additional gate to check the
+/// authentication tag.
+pub const fn decrypt_var_gates(n: usize) -> usize {
+    encrypt_var_gates(n) + 1
+}
+
+/// Mirror the implementation of [`PoseidonVarCipher::encrypt`] inside of a
+/// PLONK circuit.
+///
+/// The circuit is defined by the length of `message`. The returned set of
+/// variables is the cipher text, one scalar longer than the message.
+///
+/// [`PoseidonVarCipher::encrypt`]: crate::cipher::PoseidonVarCipher::encrypt
+pub fn encrypt_var(
+    composer: &mut TurboComposer,
+    shared_secret: &WitnessPoint,
+    nonce: Witness,
+    message: &[Witness],
+) -> Vec<Witness> {
+    let key = [*shared_secret.x(), *shared_secret.y()];
+
+    encrypt_var_with_key(composer, &key, nonce, message)
+}
+
+/// Mirror the implementation of [`PoseidonVarCipher::encrypt_with_key`]
+/// inside of a PLONK circuit.
+///
+/// [`PoseidonVarCipher::encrypt_with_key`]:
+/// crate::cipher::PoseidonVarCipher::encrypt_with_key
+pub fn encrypt_var_with_key(
+    composer: &mut TurboComposer,
+    key: &[Witness; 2],
+    nonce: Witness,
+    message: &[Witness],
+) -> Vec<Witness> {
+    let mut cipher = vec![TurboComposer::constant_zero(); message.len() + 1];
+
+    encrypt_chunks(composer, key, nonce, &[], message, &mut cipher);
 
     cipher
 }
 
-/// Given a shared secret calculated using any key protocol compatible with bls and jubjub, perform
-/// the decryption of the cipher.
+/// Mirror the implementation of [`PoseidonVarCipher::decrypt`] inside of a
+/// PLONK circuit.
 ///
-/// The returned set of variables is the original message
-pub fn decrypt(
+/// The circuit is defined by the length of `cipher`, and is not satisfied if
+/// the authentication tag doesn't match. The returned set of variables is the
+/// original message.
+///
+/// [`PoseidonVarCipher::decrypt`]: crate::cipher::PoseidonVarCipher::decrypt
+pub fn decrypt_var(
     composer: &mut TurboComposer,
     shared_secret: &WitnessPoint,
     nonce: Witness,
     cipher: &[Witness],
-) -> [Witness; PoseidonCipher::capacity()] {
-    let zero = TurboComposer::constant_zero();
+) -> Vec<Witness> {
+    let key = [*shared_secret.x(), *shared_secret.y()];
 
-    let ks0 = *shared_secret.x();
-    let ks1 = *shared_secret.y();
+    decrypt_var_with_key(composer, &key, nonce, cipher)
+}
 
-    let mut message = [zero; PoseidonCipher::capacity()];
-    let mut state =
-        PoseidonCipher::initial_state_circuit(composer, ks0, ks1, nonce);
+/// Mirror the implementation of [`PoseidonVarCipher::decrypt_with_key`]
+/// inside of a PLONK circuit.
+///
+/// Will panic if `cipher` is empty, since it must contain at least the tag.
+///
+/// [`PoseidonVarCipher::decrypt_with_key`]:
+/// crate::cipher::PoseidonVarCipher::decrypt_with_key
+pub fn decrypt_var_with_key(
+    composer: &mut TurboComposer,
+    key: &[Witness; 2],
+    nonce: Witness,
+    cipher: &[Witness],
+) -> Vec<Witness> {
+    assert!(!cipher.is_empty(), "The cipher must contain the tag");
 
-    GadgetStrategy::gadget(composer, &mut state);
+    let len = cipher.len() - 1;
+    let mut message = vec![TurboComposer::constant_zero(); len];
 
-    (0..PoseidonCipher::capacity()).for_each(|i| {
-        let constraint = Constraint::new()
-            .left(1)
-            .a(cipher[i])
-            .right(-BlsScalar::one())
-            .b(state[i + 1]);
+    decrypt_chunks(composer, key, nonce, &[], cipher, &mut message);
 
-        message[i] = composer.gate_add(constraint);
+    message
+}
 
-        state[i + 1] = cipher[i];
-    });
+/// Append the encryption of the `message` into the `cipher`, that must be one
+/// variable longer, after absorbing the associated data `ad`
+fn encrypt_chunks(
+    composer: &mut TurboComposer,
+    key: &[Witness; 2],
+    nonce: Witness,
+    ad: &[Witness],
+    message: &[Witness],
+    cipher: &mut [Witness],
+) {
+    let len = message.len();
+    let mut state = initial_state_with_len(composer, key, nonce, len, ad.len());
 
     GadgetStrategy::gadget(composer, &mut state);
+    absorb_ad(composer, &mut state, ad);
+
+    message
+        .chunks(WIDTH - 1)
+        .zip(cipher.chunks_mut(WIDTH - 1))
+        .for_each(|(m, c)| {
+            state[1..]
+                .iter_mut()
+                .zip(m.iter().zip(c.iter_mut()))
+                .for_each(|(s, (m, c))| {
+                    let constraint =
+                        Constraint::new().left(1).a(*s).right(1).b(*m);
+
+                    *s = composer.gate_add(constraint);
+                    *c = *s;
+                });
+
+            GadgetStrategy::gadget(composer, &mut state);
+        });
+
+    cipher[message.len()] = state[1];
+}
 
-    composer.assert_equal(cipher[PoseidonCipher::capacity()], state[1]);
+/// Append the decryption of the `cipher` into the `message`, that must be one
+/// variable shorter, and the check of the authentication tag, with the same
+/// associated data `ad`
+fn decrypt_chunks(
+    composer: &mut TurboComposer,
+    key: &[Witness; 2],
+    nonce: Witness,
+    ad: &[Witness],
+    cipher: &[Witness],
+    message: &mut [Witness],
+) {
+    let len = message.len();
+    let mut state = initial_state_with_len(composer, key, nonce, len, ad.len());
+
+    GadgetStrategy::gadget(composer, &mut state);
+    absorb_ad(composer, &mut state, ad);
 
     message
+        .chunks_mut(WIDTH - 1)
+        .zip(cipher.chunks(WIDTH - 1))
+        .for_each(|(m, c)| {
+            state[1..]
+                .iter_mut()
+                .zip(m.iter_mut().zip(c.iter()))
+                .for_each(|(s, (m, c))| {
+                    let constraint = Constraint::new()
+                        .left(1)
+                        .a(*c)
+                        .right(-BlsScalar::one())
+                        .b(*s);
+
+                    *m = composer.gate_add(constraint);
+                    *s = *c;
+                });
+
+            GadgetStrategy::gadget(composer, &mut state);
+        });
+
+    composer.assert_equal(cipher[len], state[1]);
 }
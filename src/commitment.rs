@@ -0,0 +1,101 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! # Dusk-Poseidon Commitment
+//!
+//! Hiding and binding commitment to a set of scalars.
+//!
+//! The commitment is the sponge hash, in the [`domain::COMMITMENT`] domain, of
+//! the values followed by the blinder. The blinder must be sampled uniformly at
+//! random for the commitment to be hiding.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use dusk_bls12_381::BlsScalar;
+//! use dusk_poseidon::commitment;
+//! use rand_core::OsRng;
+//!
+//! let values = [BlsScalar::from(100u64), BlsScalar::from(2u64)];
+//! let blinder = BlsScalar::random(&mut OsRng);
+//!
+//! let c = commitment::commit(&values, &blinder);
+//!
+//! assert!(commitment::verify_opening(&c, &values, &blinder));
+//! ```
+
+use crate::sponge::{self, domain};
+
+#[cfg(feature = "canon")]
+use canonical_derive::Canon;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::{Error as BytesError, Serializable};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Default)]
+#[cfg_attr(feature = "canon", derive(Canon))]
+/// Commitment to a set of scalars
+pub struct PoseidonCommitment {
+    commitment: BlsScalar,
+}
+
+impl Serializable<32> for PoseidonCommitment {
+    type Error = BytesError;
+
+    /// Convert the instance to a bytes representation
+    fn to_bytes(&self) -> [u8; Self::SIZE] {
+        self.commitment.to_bytes()
+    }
+
+    /// Create an instance from a previous `PoseidonCommitment::to_bytes`
+    /// function
+    fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, Self::Error> {
+        BlsScalar::from_bytes(bytes).map(Self::new)
+    }
+}
+
+impl From<PoseidonCommitment> for BlsScalar {
+    fn from(c: PoseidonCommitment) -> Self {
+        c.commitment
+    }
+}
+
+impl PoseidonCommitment {
+    /// [`PoseidonCommitment`] constructor
+    pub const fn new(commitment: BlsScalar) -> Self {
+        Self { commitment }
+    }
+
+    /// Getter for the commitment
+    pub const fn commitment(&self) -> &BlsScalar {
+        &self.commitment
+    }
+}
+
+/// Commit to the `values` with the provided `blinder`
+pub fn commit(values: &[BlsScalar], blinder: &BlsScalar) -> PoseidonCommitment {
+    let mut sponge = sponge::Sponge::with_domain(domain::COMMITMENT);
+
+    sponge.absorb_slice(values);
+    sponge.absorb(blinder);
+
+    PoseidonCommitment::new(sponge.finalize())
+}
+
+/// Check if `values` and `blinder` are an opening of the `commitment`
+pub fn verify_opening(
+    commitment: &PoseidonCommitment,
+    values: &[BlsScalar],
+    blinder: &BlsScalar,
+) -> bool {
+    &commit(values, blinder) == commitment
+}
+
+#[cfg(feature = "alloc")]
+mod zk;
+
+#[cfg(feature = "alloc")]
+pub use zk::{commit_gadget, opening_gadget, public_opening_gadget};
@@ -0,0 +1,57 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::commitment::PoseidonCommitment;
+use crate::sponge::{domain, SpongeGadget};
+
+use dusk_plonk::prelude::*;
+
+/// Mirror the implementation of [`super::commit`] inside of a PLONK circuit.
+///
+/// The returned value is the commitment witness
+pub fn commit_gadget(
+    composer: &mut TurboComposer,
+    values: &[Witness],
+    blinder: Witness,
+) -> Witness {
+    let mut sponge = SpongeGadget::with_domain(composer, domain::COMMITMENT);
+
+    sponge.absorb_slice(composer, values);
+    sponge.absorb(composer, blinder);
+
+    sponge.finalize(composer)
+}
+
+/// Prove the knowledge of an opening of a witnessed commitment
+pub fn opening_gadget(
+    composer: &mut TurboComposer,
+    commitment: Witness,
+    values: &[Witness],
+    blinder: Witness,
+) {
+    let c = commit_gadget(composer, values, blinder);
+
+    composer.assert_equal(c, commitment);
+}
+
+/// Prove the knowledge of an opening of a public commitment
+///
+/// The commitment is appended as a public input, so it must be provided as
+/// such to the verifier.
+pub fn public_opening_gadget(
+    composer: &mut TurboComposer,
+    commitment: &PoseidonCommitment,
+    values: &[Witness],
+    blinder: Witness,
+) {
+    let c = commit_gadget(composer, values, blinder);
+
+    composer.assert_equal_constant(
+        c,
+        BlsScalar::zero(),
+        Some(*commitment.commitment()),
+    );
+}
@@ -21,6 +21,10 @@ pub enum Error {
     TreeIterFailed,
     /// Decryption failed for the provided secret+nonce
     CipherDecryptionFailed,
+    /// A sponge call didn't follow the declared IO pattern
+    IOPatternViolation,
+    /// Decryption failed for the chunk of a stream with the provided index
+    StreamChunkFailed(u64),
 }
 
 impl Display for Error {
@@ -0,0 +1,120 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! # Key derivation
+//!
+//! Derive labelled sub-keys from a shared secret, such as the one computed by
+//! a Diffie-Hellman key exchange.
+//!
+//! A sub-key is the sponge hash, in the [`domain::KDF`] domain, of the
+//! coordinates of the shared secret, an arbitrary context, the label of the
+//! sub-key and its index. Since the label and the index have a fixed length,
+//! the encoding is unambiguous for contexts of any length.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use core::ops::Mul;
+//! use dusk_bls12_381::BlsScalar;
+//! use dusk_jubjub::{dhke, JubJubScalar, GENERATOR};
+//! use dusk_poseidon::cipher::PoseidonCipher;
+//! use dusk_poseidon::kdf;
+//! use rand_core::OsRng;
+//!
+//! let sender_secret = JubJubScalar::random(&mut OsRng);
+//! let receiver_secret = JubJubScalar::random(&mut OsRng);
+//! let receiver_public = GENERATOR.to_niels().mul(&receiver_secret);
+//!
+//! let shared_secret = dhke(&sender_secret, &receiver_public);
+//! let context = [BlsScalar::from(42u64)];
+//!
+//! let key = kdf::cipher_key(&shared_secret, &context);
+//! let nonce = BlsScalar::random(&mut OsRng);
+//!
+//! let message = [BlsScalar::one(), BlsScalar::from(2u64)];
+//! let cipher = PoseidonCipher::encrypt_with_key(&message, &key, &nonce);
+//!
+//! assert_eq!(message, cipher.decrypt_with_key(&key, &nonce).unwrap());
+//! ```
+
+use crate::sponge::{domain, Sponge};
+
+use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::JubJubAffine;
+
+/// Label for encryption keys
+pub const ENCRYPTION: u64 = 1;
+
+/// Label for MAC keys
+pub const MAC: u64 = 2;
+
+/// Label for nonce seeds
+pub const NONCE: u64 = 3;
+
+/// Key derivation midstate for a shared secret and a context
+///
+/// The shared secret and the context are absorbed only once, and every
+/// sub-key is derived from a copy of the resulting sponge.
+#[derive(Debug, Clone)]
+pub struct Kdf {
+    sponge: Sponge,
+}
+
+impl Kdf {
+    /// Create a new key derivation for the shared secret and context
+    pub fn new(secret: &JubJubAffine, context: &[BlsScalar]) -> Self {
+        let mut sponge = Sponge::with_domain(domain::KDF);
+
+        sponge.absorb(&secret.get_x());
+        sponge.absorb(&secret.get_y());
+        sponge.absorb_slice(context);
+
+        Self { sponge }
+    }
+
+    /// Derive the sub-key for the label and index
+    pub fn derive(&self, label: u64, index: u64) -> BlsScalar {
+        let mut sponge = self.sponge.clone();
+
+        sponge.absorb(&BlsScalar::from(label));
+        sponge.absorb(&BlsScalar::from(index));
+
+        sponge.finalize()
+    }
+
+    /// Derive the key to be used with [`crate::cipher::PoseidonCipher`]
+    ///
+    /// The key is composed of the [`ENCRYPTION`] sub-keys of index `0` and
+    /// `1`.
+    pub fn cipher_key(&self) -> [BlsScalar; 2] {
+        [self.derive(ENCRYPTION, 0), self.derive(ENCRYPTION, 1)]
+    }
+}
+
+/// Derive the sub-key for the shared secret, context, label and index
+pub fn derive(
+    secret: &JubJubAffine,
+    context: &[BlsScalar],
+    label: u64,
+    index: u64,
+) -> BlsScalar {
+    Kdf::new(secret, context).derive(label, index)
+}
+
+/// Derive the key to be used with [`crate::cipher::PoseidonCipher`] for the
+/// shared secret and context
+pub fn cipher_key(
+    secret: &JubJubAffine,
+    context: &[BlsScalar],
+) -> [BlsScalar; 2] {
+    Kdf::new(secret, context).cipher_key()
+}
+
+#[cfg(feature = "alloc")]
+mod zk;
+
+#[cfg(feature = "alloc")]
+pub use zk::{cipher_key_gadget, derive_gadget, KdfGadget};
@@ -0,0 +1,82 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::ENCRYPTION;
+use crate::sponge::{domain, SpongeGadget};
+
+use dusk_plonk::prelude::*;
+
+/// Mirror the implementation of [`super::Kdf`] inside of a PLONK circuit.
+///
+/// The labels and indexes are appended as circuit constants.
+#[derive(Debug, Clone)]
+pub struct KdfGadget {
+    sponge: SpongeGadget,
+}
+
+impl KdfGadget {
+    /// Create a new key derivation gadget for the shared secret and context
+    pub fn new(
+        composer: &mut TurboComposer,
+        secret: &WitnessPoint,
+        context: &[Witness],
+    ) -> Self {
+        let mut sponge = SpongeGadget::with_domain(composer, domain::KDF);
+
+        sponge.absorb(composer, *secret.x());
+        sponge.absorb(composer, *secret.y());
+        sponge.absorb_slice(composer, context);
+
+        Self { sponge }
+    }
+
+    /// Derive the sub-key for the label and index
+    pub fn derive(
+        &self,
+        composer: &mut TurboComposer,
+        label: u64,
+        index: u64,
+    ) -> Witness {
+        let mut sponge = self.sponge.clone();
+
+        let label = composer.append_constant(BlsScalar::from(label));
+        let index = composer.append_constant(BlsScalar::from(index));
+
+        sponge.absorb(composer, label);
+        sponge.absorb(composer, index);
+
+        sponge.finalize(composer)
+    }
+
+    /// Derive the key to be used with [`crate::cipher::encrypt_with_key`]
+    pub fn cipher_key(&self, composer: &mut TurboComposer) -> [Witness; 2] {
+        [
+            self.derive(composer, ENCRYPTION, 0),
+            self.derive(composer, ENCRYPTION, 1),
+        ]
+    }
+}
+
+/// Mirror the implementation of [`super::derive`] inside of a PLONK circuit.
+pub fn derive_gadget(
+    composer: &mut TurboComposer,
+    secret: &WitnessPoint,
+    context: &[Witness],
+    label: u64,
+    index: u64,
+) -> Witness {
+    KdfGadget::new(composer, secret, context).derive(composer, label, index)
+}
+
+/// Mirror the implementation of [`super::cipher_key`] inside of a PLONK
+/// circuit.
+pub fn cipher_key_gadget(
+    composer: &mut TurboComposer,
+    secret: &WitnessPoint,
+    context: &[Witness],
+) -> [Witness; 2] {
+    KdfGadget::new(composer, secret, context).cipher_key(composer)
+}
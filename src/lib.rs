@@ -14,12 +14,31 @@ extern crate alloc;
 /// Encryption and decryption implementation over a Poseidon cipher
 pub mod cipher;
 
+/// Commitment scheme over the Poseidon sponge
+pub mod commitment;
+
+/// Key derivation from a shared secret
+pub mod kdf;
+
+/// Message authentication codes over the Poseidon sponge
+pub mod mac;
+
 /// Module containing a fixed-length Poseidon hash implementation
 pub mod perm_uses;
 
+/// Deterministic random number generator over the Poseidon permutation
+pub mod rng;
+
 /// Reference implementation for the Poseidon Sponge hash function
 pub mod sponge;
 
+/// Fiat-Shamir transcript over the Poseidon permutation
+pub mod transcript;
+
+/// Known-answer vectors for implementations in other languages
+#[cfg(feature = "alloc")]
+pub mod vectors;
+
 mod error;
 /// The module handling posedion-trees.
 #[cfg(feature = "canon")]
@@ -0,0 +1,65 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! # Dusk-Poseidon MAC
+//!
+//! Keyed hash to authenticate a message of scalars.
+//!
+//! The tag is the sponge hash, in the [`domain::MAC`] domain, of the key
+//! followed by the message. Since the key always takes the first element of
+//! the rate, the encoding is unambiguous for messages of any length. A key can
+//! be derived from a shared secret with the [`crate::kdf::MAC`] label.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use dusk_bls12_381::BlsScalar;
+//! use dusk_poseidon::mac;
+//! use rand_core::OsRng;
+//!
+//! let key = BlsScalar::random(&mut OsRng);
+//! let message = [BlsScalar::from(100u64), BlsScalar::from(2u64)];
+//!
+//! let tag = mac::mac(&key, &message);
+//!
+//! assert!(mac::verify(&key, &message, &tag));
+//! assert!(!mac::verify(&key, &message[..1], &tag));
+//! ```
+
+use crate::sponge::{domain, Sponge};
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+
+/// Compute the authentication tag of the `message` under the `key`
+pub fn mac(key: &BlsScalar, message: &[BlsScalar]) -> BlsScalar {
+    let mut sponge = Sponge::with_domain(domain::MAC);
+
+    sponge.absorb(key);
+    sponge.absorb_slice(message);
+
+    sponge.finalize()
+}
+
+/// Check if `tag` authenticates the `message` under the `key`
+///
+/// The tags are compared in constant time.
+pub fn verify(key: &BlsScalar, message: &[BlsScalar], tag: &BlsScalar) -> bool {
+    let expected = mac(key, message).to_bytes();
+    let tag = tag.to_bytes();
+
+    expected
+        .iter()
+        .zip(tag.iter())
+        .fold(0u8, |acc, (e, t)| acc | (e ^ t))
+        == 0
+}
+
+#[cfg(feature = "alloc")]
+mod zk;
+
+#[cfg(feature = "alloc")]
+pub use zk::{mac_gadget, verify_gadget};
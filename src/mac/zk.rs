@@ -0,0 +1,37 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::sponge::{domain, SpongeGadget};
+
+use dusk_plonk::prelude::*;
+
+/// Mirror the implementation of [`super::mac`] inside of a PLONK circuit.
+///
+/// The returned value is the tag witness
+pub fn mac_gadget(
+    composer: &mut TurboComposer,
+    key: Witness,
+    message: &[Witness],
+) -> Witness {
+    let mut sponge = SpongeGadget::with_domain(composer, domain::MAC);
+
+    sponge.absorb(composer, key);
+    sponge.absorb_slice(composer, message);
+
+    sponge.finalize(composer)
+}
+
+/// Prove that `tag` authenticates the `message` under the secret `key`
+pub fn verify_gadget(
+    composer: &mut TurboComposer,
+    key: Witness,
+    message: &[Witness],
+    tag: Witness,
+) {
+    let t = mac_gadget(composer, key, message);
+
+    composer.assert_equal(t, tag);
+}
@@ -7,7 +7,47 @@
 //! The `pad` module implements the padding algorithm on the Poseidon hash.
 
 use dusk_bls12_381::BlsScalar;
-use dusk_hades::{ScalarStrategy, Strategy};
+use dusk_hades::{ScalarStrategy, Strategy, WIDTH};
+
+#[cfg(feature = "alloc")]
+mod zk;
+
+#[cfg(feature = "alloc")]
+pub use zk::{
+    hash1_gadget, hash2_gadget, hash3_gadget, hash4_gadget, hash_fixed_gates,
+    xof_gadget,
+};
+
+/// Returns the capacity element used by [`xof`] for an input of length `len`
+///
+/// The length is encoded as `len · 2^64`, following the fixed-length domain
+/// separation of the Poseidon paper.
+pub(crate) const fn xof_capacity(len: usize) -> BlsScalar {
+    BlsScalar::from_raw([0, len as u64, 0, 0])
+}
+
+/// Returns the state of [`xof`] after absorbing `input`, ready to be squeezed
+/// from the rate elements
+pub(crate) fn xof_absorb(input: &[BlsScalar]) -> [BlsScalar; WIDTH] {
+    let mut h = ScalarStrategy::new();
+    let mut state = [BlsScalar::zero(); WIDTH];
+
+    state[0] = xof_capacity(input.len());
+
+    if input.is_empty() {
+        h.perm(&mut state);
+    }
+
+    input.chunks(WIDTH - 1).for_each(|chunk| {
+        state[1..].iter_mut().zip(chunk.iter()).for_each(|(s, c)| {
+            *s += c;
+        });
+
+        h.perm(&mut state);
+    });
+
+    state
+}
 
 /// Takes in one BlsScalar and outputs 2.
 /// This function is fixed.
@@ -27,6 +67,71 @@ pub fn two_outputs(message: BlsScalar) -> [BlsScalar; 2] {
     [words[1], words[2]]
 }
 
+/// Fixed-length hash of `N < WIDTH` scalars with a single permutation.
+///
+/// The capacity element is `N · 2^64`, as in the `ConstantLength` domain of
+/// the Poseidon paper for a single output, and no padding is appended.
+fn hash_fixed<const N: usize>(message: &[BlsScalar; N]) -> BlsScalar {
+    let mut state = [BlsScalar::zero(); WIDTH];
+
+    state[0] = xof_capacity(N);
+    state[1..N + 1].copy_from_slice(message);
+
+    ScalarStrategy::new().perm(&mut state);
+
+    state[1]
+}
+
+/// Hash a single scalar with exactly one permutation.
+///
+/// The result is the same as the first output of [`two_outputs`].
+pub fn hash1(message: &[BlsScalar; 1]) -> BlsScalar {
+    hash_fixed(message)
+}
+
+/// Hash two scalars with exactly one permutation.
+pub fn hash2(message: &[BlsScalar; 2]) -> BlsScalar {
+    hash_fixed(message)
+}
+
+/// Hash three scalars with exactly one permutation.
+pub fn hash3(message: &[BlsScalar; 3]) -> BlsScalar {
+    hash_fixed(message)
+}
+
+/// Hash four scalars with exactly one permutation.
+pub fn hash4(message: &[BlsScalar; 4]) -> BlsScalar {
+    hash_fixed(message)
+}
+
+/// Extendable output function: takes an arbitrary number of input scalars
+/// and fills `output` with an arbitrary number of output scalars.
+///
+/// The length of the input is encoded in the capacity element, so no padding
+/// is appended. The input is absorbed in chunks of `WIDTH - 1` elements with a
+/// permutation after each chunk, and a single permutation is performed for an
+/// empty input. The output is then squeezed from the rate elements, with a
+/// permutation between every chunk of `WIDTH - 1` outputs.
+///
+/// The number of outputs is not part of the domain, so a shorter output is
+/// always a prefix of a longer one for the same input. For a single input and
+/// two outputs, the result is the same as [`two_outputs`].
+pub fn xof(input: &[BlsScalar], output: &mut [BlsScalar]) {
+    let mut h = ScalarStrategy::new();
+    let mut state = xof_absorb(input);
+
+    output
+        .chunks_mut(WIDTH - 1)
+        .enumerate()
+        .for_each(|(i, chunk)| {
+            if i > 0 {
+                h.perm(&mut state);
+            }
+
+            chunk.copy_from_slice(&state[1..chunk.len() + 1]);
+        });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,4 +160,62 @@ mod tests {
             assert_eq!(h, h_1);
         }
     }
+
+    #[test]
+    fn xof_two_outputs() {
+        let m = BlsScalar::random(&mut OsRng);
+
+        let mut h = [BlsScalar::zero(); 2];
+        xof(&[m], &mut h);
+
+        assert_eq!(two_outputs(m), h);
+    }
+
+    #[test]
+    fn hash_fixed_length() {
+        let mut m = [BlsScalar::zero(); 4];
+        m.iter_mut()
+            .for_each(|m| *m = BlsScalar::random(&mut OsRng));
+
+        let mut h = [BlsScalar::zero(); 1];
+
+        xof(&m[..1], &mut h);
+        assert_eq!(h[0], hash1(&[m[0]]));
+        assert_eq!(h[0], two_outputs(m[0])[0]);
+
+        xof(&m[..2], &mut h);
+        assert_eq!(h[0], hash2(&[m[0], m[1]]));
+
+        xof(&m[..3], &mut h);
+        assert_eq!(h[0], hash3(&[m[0], m[1], m[2]]));
+
+        xof(&m, &mut h);
+        assert_eq!(h[0], hash4(&m));
+
+        // The length is part of the domain
+        assert_ne!(hash1(&[m[0]]), hash2(&[m[0], BlsScalar::zero()]));
+    }
+
+    #[test]
+    fn xof_prefix() {
+        let mut input = [BlsScalar::zero(); 6];
+        input
+            .iter_mut()
+            .for_each(|i| *i = BlsScalar::random(&mut OsRng));
+
+        let mut long = [BlsScalar::zero(); 11];
+        xof(&input, &mut long);
+
+        for n in 0..long.len() {
+            let mut short = [BlsScalar::zero(); 11];
+            xof(&input, &mut short[..n]);
+
+            assert_eq!(&long[..n], &short[..n]);
+        }
+
+        let mut other = [BlsScalar::zero(); 11];
+        xof(&input[..5], &mut other);
+
+        assert_ne!(long, other);
+    }
 }
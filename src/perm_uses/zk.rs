@@ -0,0 +1,120 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::sponge::PERMUTATION_GATES;
+
+use dusk_hades::{GadgetStrategy, WIDTH};
+
+use dusk_plonk::prelude::*;
+
+/// Mirror the implementation of [`super::xof`] inside of a PLONK circuit.
+///
+/// The circuit will be defined by the length of `input` and `output`. The
+/// capacity element is appended as a circuit constant.
+///
+/// Every chunk of `WIDTH - 1` inputs or outputs after the first costs one
+/// additional permutation.
+pub fn xof_gadget(
+    composer: &mut TurboComposer,
+    input: &[Witness],
+    output: &mut [Witness],
+) {
+    let zero = TurboComposer::constant_zero();
+    let mut state = [zero; WIDTH];
+
+    state[0] = composer.append_constant(super::xof_capacity(input.len()));
+
+    if input.is_empty() {
+        GadgetStrategy::gadget(composer, &mut state);
+    }
+
+    input.chunks(WIDTH - 1).for_each(|chunk| {
+        state[1..].iter_mut().zip(chunk.iter()).for_each(|(s, c)| {
+            let constraint = Constraint::new().left(1).a(*s).right(1).b(*c);
+
+            *s = composer.gate_add(constraint);
+        });
+
+        GadgetStrategy::gadget(composer, &mut state);
+    });
+
+    output
+        .chunks_mut(WIDTH - 1)
+        .enumerate()
+        .for_each(|(i, chunk)| {
+            if i > 0 {
+                GadgetStrategy::gadget(composer, &mut state);
+            }
+
+            chunk.copy_from_slice(&state[1..chunk.len() + 1]);
+        });
+}
+
+/// Number of gates appended to the circuit by any of [`hash1_gadget`],
+/// [`hash2_gadget`], [`hash3_gadget`] and [`hash4_gadget`]
+///
+/// The gadgets append one gate for the capacity constant, and the
+/// [`PERMUTATION_GATES`] of a single permutation.
+pub const fn hash_fixed_gates() -> usize {
+    1 + PERMUTATION_GATES
+}
+
+/// Mirror the fixed-length hash of `N < WIDTH` scalars inside of a PLONK
+/// circuit.
+fn hash_fixed_gadget<const N: usize>(
+    composer: &mut TurboComposer,
+    message: &[Witness; N],
+) -> Witness {
+    let zero = TurboComposer::constant_zero();
+    let mut state = [zero; WIDTH];
+
+    state[0] = composer.append_constant(super::xof_capacity(N));
+    state[1..N + 1].copy_from_slice(message);
+
+    GadgetStrategy::gadget(composer, &mut state);
+
+    state[1]
+}
+
+/// Mirror the implementation of [`super::hash1`] inside of a PLONK circuit.
+///
+/// The gadget appends [`hash_fixed_gates`] gates.
+pub fn hash1_gadget(
+    composer: &mut TurboComposer,
+    message: &[Witness; 1],
+) -> Witness {
+    hash_fixed_gadget(composer, message)
+}
+
+/// Mirror the implementation of [`super::hash2`] inside of a PLONK circuit.
+///
+/// The gadget appends [`hash_fixed_gates`] gates.
+pub fn hash2_gadget(
+    composer: &mut TurboComposer,
+    message: &[Witness; 2],
+) -> Witness {
+    hash_fixed_gadget(composer, message)
+}
+
+/// Mirror the implementation of [`super::hash3`] inside of a PLONK circuit.
+///
+/// The gadget appends [`hash_fixed_gates`] gates.
+pub fn hash3_gadget(
+    composer: &mut TurboComposer,
+    message: &[Witness; 3],
+) -> Witness {
+    hash_fixed_gadget(composer, message)
+}
+
+/// Mirror the implementation of [`super::hash4`] inside of a PLONK circuit.
+///
+/// The gadget appends [`hash_fixed_gates`] gates.
+pub fn hash4_gadget(
+    composer: &mut TurboComposer,
+    message: &[Witness; 4],
+) -> Witness {
+    hash_fixed_gadget(composer, message)
+}
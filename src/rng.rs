@@ -0,0 +1,116 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! # Dusk-Poseidon RNG
+//!
+//! Seedable CSPRNG whose output can be re-derived inside of a circuit.
+//!
+//! The stream of scalars of [`PoseidonRng`] is the output of
+//! [`crate::perm_uses::xof`] for the seed, so the first `n` scalars are the
+//! same as the ones of `xof(seed, &mut [BlsScalar; n])`. Every permutation
+//! produces `WIDTH - 1` scalars.
+//!
+//! The bytes of [`RngCore`] are taken from the 128 least significant bits of
+//! the scalars of the same stream, so their distribution is indistinguishable
+//! from uniform.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use dusk_bls12_381::BlsScalar;
+//! use dusk_jubjub::JubJubScalar;
+//! use dusk_poseidon::rng::PoseidonRng;
+//!
+//! let seed = [BlsScalar::from(42u64)];
+//!
+//! let mut rng = PoseidonRng::new(&seed);
+//! let blinder = rng.next_scalar();
+//! let secret = JubJubScalar::random(&mut rng);
+//!
+//! let mut rng = PoseidonRng::new(&seed);
+//! assert_eq!(blinder, rng.next_scalar());
+//! assert_eq!(secret, JubJubScalar::random(&mut rng));
+//! ```
+
+use crate::perm_uses;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_hades::{ScalarStrategy, Strategy, WIDTH};
+use rand_core::{impls, CryptoRng, Error, RngCore};
+
+/// Number of bytes of [`RngCore`] output taken from every scalar
+const BYTES_PER_OUTPUT: usize = 16;
+
+/// Deterministic random number generator seeded from scalars
+#[derive(Debug, Clone)]
+pub struct PoseidonRng {
+    state: [BlsScalar; WIDTH],
+    pos: usize,
+    bytes: [u8; BYTES_PER_OUTPUT],
+    bytes_pos: usize,
+}
+
+impl PoseidonRng {
+    /// Create a new generator for the provided seed
+    pub fn new(seed: &[BlsScalar]) -> Self {
+        Self {
+            state: perm_uses::xof_absorb(seed),
+            pos: 0,
+            bytes: [0u8; BYTES_PER_OUTPUT],
+            bytes_pos: BYTES_PER_OUTPUT,
+        }
+    }
+
+    /// Return the next scalar of the stream
+    pub fn next_scalar(&mut self) -> BlsScalar {
+        if self.pos == WIDTH - 1 {
+            ScalarStrategy::new().perm(&mut self.state);
+            self.pos = 0;
+        }
+
+        self.pos += 1;
+        self.state[self.pos]
+    }
+}
+
+impl RngCore for PoseidonRng {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.iter_mut().for_each(|b| {
+            if self.bytes_pos == BYTES_PER_OUTPUT {
+                let scalar = self.next_scalar().to_bytes();
+
+                self.bytes.copy_from_slice(&scalar[..BYTES_PER_OUTPUT]);
+                self.bytes_pos = 0;
+            }
+
+            *b = self.bytes[self.bytes_pos];
+            self.bytes_pos += 1;
+        });
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+
+        Ok(())
+    }
+}
+
+impl CryptoRng for PoseidonRng {}
+
+#[cfg(feature = "alloc")]
+mod zk;
+
+#[cfg(feature = "alloc")]
+pub use zk::gadget;
@@ -0,0 +1,22 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::perm_uses::xof_gadget;
+
+use dusk_plonk::prelude::*;
+
+/// Reproduce the first `output.len()` scalars of [`super::PoseidonRng`] for
+/// the `seed` inside of a PLONK circuit.
+///
+/// The outputs are the ones of [`super::PoseidonRng::next_scalar`], so the
+/// circuit will be defined by the length of `seed` and `output`.
+pub fn gadget(
+    composer: &mut TurboComposer,
+    seed: &[Witness],
+    output: &mut [Witness],
+) {
+    xof_gadget(composer, seed, output)
+}
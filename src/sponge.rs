@@ -4,14 +4,35 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+mod bytes;
 mod hash;
 
+#[cfg(feature = "alloc")]
+mod batch;
+
 #[cfg(feature = "alloc")]
 mod gadget;
 
+pub mod domain;
+pub mod point;
+pub mod safe;
 pub mod truncated;
 
-pub use hash::hash;
+pub use bytes::{hash_bytes, BYTES_PER_SCALAR};
+pub use hash::{hash, hash_with_domain, Sponge};
+
+#[cfg(feature = "alloc")]
+pub(crate) use bytes::pack;
+
+#[cfg(feature = "alloc")]
+pub use batch::{hash_leaves, hash_many, hash_many_with_domain};
+
+#[cfg(feature = "alloc")]
+pub use bytes::gadget_bytes;
 
 #[cfg(feature = "alloc")]
-pub use gadget::gadget;
+pub use gadget::{
+    gadget, gadget_gates, gadget_mixed, gadget_mixed_with_domain,
+    gadget_var_len, gadget_with_domain, GadgetInput, SpongeGadget,
+    PERMUTATION_GATES,
+};
@@ -0,0 +1,43 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Batch hashing of independent messages
+//!
+//! With the `parallel` feature, the messages are hashed across threads. The
+//! results are the same, and in the same order, as the serial path.
+
+use super::{domain, hash_with_domain};
+
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Apply [`super::hash`] to every message of `messages`
+pub fn hash_many(messages: &[&[BlsScalar]]) -> Vec<BlsScalar> {
+    hash_many_with_domain(domain::DEFAULT, messages)
+}
+
+/// Apply [`hash_with_domain`] for [`domain::MERKLE_LEAF`] to every message of
+/// `messages`
+pub fn hash_leaves(messages: &[&[BlsScalar]]) -> Vec<BlsScalar> {
+    hash_many_with_domain(domain::MERKLE_LEAF, messages)
+}
+
+/// Apply [`hash_with_domain`] to every message of `messages`
+pub fn hash_many_with_domain(
+    domain: u64,
+    messages: &[&[BlsScalar]],
+) -> Vec<BlsScalar> {
+    #[cfg(feature = "parallel")]
+    let messages = messages.par_iter();
+
+    #[cfg(not(feature = "parallel"))]
+    let messages = messages.iter();
+
+    messages.map(|m| hash_with_domain(domain, m)).collect()
+}
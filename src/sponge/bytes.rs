@@ -0,0 +1,112 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Sponge hash and gadget definition for byte strings
+//!
+//! The bytes are packed into scalars in chunks of [`BYTES_PER_SCALAR`], with
+//! every chunk read as a little-endian integer. Since `2^248` is smaller than
+//! the field modulus, every chunk maps to a distinct scalar. The last chunk
+//! may be shorter, and its missing bytes are zeroes.
+//!
+//! The sequence hashed by the sponge is the number of bytes followed by the
+//! packed chunks, so byte strings that differ only by trailing zeroes are also
+//! mapped to distinct sequences.
+
+use super::Sponge;
+
+use dusk_bls12_381::BlsScalar;
+
+#[cfg(feature = "alloc")]
+use super::SpongeGadget;
+#[cfg(feature = "alloc")]
+use dusk_plonk::prelude::*;
+
+/// Number of bytes packed into a single scalar
+pub const BYTES_PER_SCALAR: usize = 31;
+
+/// Pack up to [`BYTES_PER_SCALAR`] bytes into a scalar, as a little-endian
+/// integer
+pub(crate) fn pack(bytes: &[u8]) -> BlsScalar {
+    debug_assert!(bytes.len() <= BYTES_PER_SCALAR);
+
+    let mut buf = [0u8; 32];
+    buf[..bytes.len()].copy_from_slice(bytes);
+
+    let mut limbs = [0u64; 4];
+    limbs.iter_mut().zip(buf.chunks(8)).for_each(|(l, b)| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(b);
+
+        *l = u64::from_le_bytes(bytes);
+    });
+
+    BlsScalar::from_raw(limbs)
+}
+
+/// Hash an arbitrary byte string
+///
+/// The result is [`super::hash`] applied to `[bytes.len(), p_0, p_1, ..]`,
+/// where `p_i` are the bytes packed as described in the module documentation.
+pub fn hash_bytes(bytes: &[u8]) -> BlsScalar {
+    let mut sponge = Sponge::new();
+
+    sponge.absorb(&BlsScalar::from(bytes.len() as u64));
+    bytes
+        .chunks(BYTES_PER_SCALAR)
+        .for_each(|chunk| sponge.absorb(&pack(chunk)));
+
+    sponge.finalize()
+}
+
+/// Pack up to [`BYTES_PER_SCALAR`] byte witnesses into a scalar witness, as a
+/// little-endian integer
+///
+/// The byte witnesses are not range constrained.
+#[cfg(feature = "alloc")]
+pub(crate) fn pack_gadget(
+    composer: &mut TurboComposer,
+    bytes: &[Witness],
+) -> Witness {
+    debug_assert!(bytes.len() <= BYTES_PER_SCALAR);
+
+    let mut coefficient = BlsScalar::one();
+    let mut packed = TurboComposer::constant_zero();
+
+    bytes.iter().for_each(|b| {
+        let constraint =
+            Constraint::new().left(1).a(packed).right(coefficient).b(*b);
+        packed = composer.gate_add(constraint);
+
+        coefficient *= BlsScalar::from(256u64);
+    });
+
+    packed
+}
+
+/// Mirror the implementation of [`hash_bytes`] inside of a PLONK circuit.
+///
+/// Every witness of `bytes` is constrained to 8 bits. The circuit will be
+/// defined by the length of `bytes`, and the length is appended as a circuit
+/// constant.
+#[cfg(feature = "alloc")]
+pub fn gadget_bytes(
+    composer: &mut TurboComposer,
+    bytes: &[Witness],
+) -> Witness {
+    bytes.iter().for_each(|b| composer.component_range(*b, 8));
+
+    let mut sponge = SpongeGadget::new();
+
+    let len = composer.append_constant(BlsScalar::from(bytes.len() as u64));
+    sponge.absorb(composer, len);
+
+    bytes.chunks(BYTES_PER_SCALAR).for_each(|chunk| {
+        let packed = pack_gadget(composer, chunk);
+        sponge.absorb(composer, packed);
+    });
+
+    sponge.finalize(composer)
+}
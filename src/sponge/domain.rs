@@ -0,0 +1,49 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Registry of well-known domain tags for [`super::hash_with_domain`]
+//!
+//! The domain is mixed into the capacity element as `domain · 2^128`. This
+//! keeps it apart from the other capacity values of the crate, such as the
+//! Merkle tree bitmask, the cipher domain `2^32` and the fixed-length encoding
+//! `len · 2^64` of [`crate::perm_uses`].
+//!
+//! The Merkle tree nodes and the cipher keep their own capacity values for
+//! backward compatibility, so their hashes and ciphers don't change. The
+//! [`MERKLE_NODE`] and [`CIPHER`] tags are reserved for them, and must not be
+//! assigned to any other domain.
+
+use dusk_bls12_381::BlsScalar;
+
+/// Domain of [`super::hash`]
+pub const DEFAULT: u64 = 0;
+
+/// Domain reserved for Merkle tree nodes, that are hashed with the bitmask of
+/// their children as capacity element
+pub const MERKLE_NODE: u64 = 1;
+
+/// Domain for Merkle tree leaves
+pub const MERKLE_LEAF: u64 = 2;
+
+/// Domain for commitments
+pub const COMMITMENT: u64 = 3;
+
+/// Domain reserved for encryption, that uses `2^32` as capacity element
+pub const CIPHER: u64 = 4;
+
+/// Domain for key derivation
+pub const KDF: u64 = 5;
+
+/// Domain for message authentication codes
+pub const MAC: u64 = 6;
+
+/// Domain for Fiat-Shamir transcripts
+pub const TRANSCRIPT: u64 = 7;
+
+/// Returns the capacity element for the provided domain
+pub(crate) const fn capacity(domain: u64) -> BlsScalar {
+    BlsScalar::from_raw([0, 0, domain, 0])
+}
@@ -4,11 +4,49 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use dusk_hades::{GadgetStrategy, WIDTH};
+use alloc::vec;
+
+use dusk_hades::{GadgetStrategy, ScalarStrategy, Strategy};
+use dusk_hades::{PARTIAL_ROUNDS, TOTAL_FULL_ROUNDS, WIDTH};
 
 use dusk_plonk::prelude::*;
 
-/// Mirror the implementation of [`super::sponge_hash`] inside of a PLONK circuit.
+use super::domain;
+
+/// Append the capacity element of `domain` to the circuit
+fn capacity(composer: &mut TurboComposer, domain: u64) -> Witness {
+    match domain {
+        domain::DEFAULT => TurboComposer::constant_zero(),
+        _ => composer.append_constant(domain::capacity(domain)),
+    }
+}
+
+/// Number of gates appended to the circuit by a single permutation of
+/// [`GadgetStrategy::gadget`]
+///
+/// The round keys are added to the initial state with one gate per element,
+/// and folded into the matrix multiplication for the remaining rounds. Each
+/// S-box takes three multiplication gates and each row of the matrix two
+/// addition gates. The full rounds apply the S-box to the whole state, while
+/// the partial rounds apply it to a single element.
+pub const PERMUTATION_GATES: usize = WIDTH
+    + TOTAL_FULL_ROUNDS * (3 * WIDTH + 2 * WIDTH)
+    + PARTIAL_ROUNDS * (3 + 2 * WIDTH);
+
+/// Number of gates appended to the circuit by [`gadget`] for a message of `n`
+/// witnesses
+///
+/// Every message and the padding take an addition gate, and the padded
+/// message is absorbed in `n / (WIDTH - 1) + 1` permutations. The empty
+/// message appends no gate.
+pub const fn gadget_gates(n: usize) -> usize {
+    match n {
+        0 => 0,
+        _ => n + 1 + (n / (WIDTH - 1) + 1) * PERMUTATION_GATES,
+    }
+}
+
+/// Mirror the implementation of [`super::hash`] inside of a PLONK circuit.
 ///
 /// The circuit will be defined by the length of `messages`. This means that a
 /// pre-computed circuit will not behave generically for different messages
@@ -20,9 +58,37 @@ use dusk_plonk::prelude::*;
 ///
 /// The returned value is the hashed witness data computed as a variable.
 pub fn gadget(composer: &mut TurboComposer, messages: &[Witness]) -> Witness {
+    gadget_with_domain(composer, domain::DEFAULT, messages)
+}
+
+/// Mirror the implementation of [`super::hash_with_domain`] inside of a PLONK
+/// circuit.
+///
+/// The capacity element is appended as a circuit constant, except for
+/// [`domain::DEFAULT`] that uses the constant zero witness.
+pub fn gadget_with_domain(
+    composer: &mut TurboComposer,
+    domain: u64,
+    messages: &[Witness],
+) -> Witness {
     let zero = TurboComposer::constant_zero();
     let mut state = [zero; WIDTH];
 
+    state[0] = capacity(composer, domain);
+
+    if messages.is_empty() {
+        if domain == domain::DEFAULT {
+            return zero;
+        }
+
+        let constraint = Constraint::new().left(1).a(state[1]).constant(1);
+
+        state[1] = composer.gate_add(constraint);
+        GadgetStrategy::gadget(composer, &mut state);
+
+        return state[1];
+    }
+
     let l = messages.len();
     let m = l / (WIDTH - 1);
     let n = m * (WIDTH - 1);
@@ -59,3 +125,373 @@ pub fn gadget(composer: &mut TurboComposer, messages: &[Witness]) -> Witness {
 
     state[1]
 }
+
+/// Input of [`gadget_mixed`], either a circuit constant or a witness
+#[derive(Debug, Clone, Copy)]
+pub enum GadgetInput {
+    /// Value known when the circuit is defined, such as a domain tag or a
+    /// fixed parameter
+    Constant(BlsScalar),
+    /// Value only known to the prover
+    Witness(Witness),
+}
+
+impl From<BlsScalar> for GadgetInput {
+    fn from(constant: BlsScalar) -> Self {
+        Self::Constant(constant)
+    }
+}
+
+impl From<Witness> for GadgetInput {
+    fn from(witness: Witness) -> Self {
+        Self::Witness(witness)
+    }
+}
+
+impl GadgetInput {
+    /// Add two inputs, folding the constants into the constant term of the
+    /// gate
+    ///
+    /// No gate is appended if both inputs are constant, or if one of them is
+    /// the constant zero.
+    fn add(self, composer: &mut TurboComposer, other: Self) -> Self {
+        use GadgetInput::{Constant, Witness};
+
+        match (self, other) {
+            (Constant(a), Constant(b)) => Constant(a + b),
+            (Constant(c), Witness(w)) | (Witness(w), Constant(c)) => {
+                if c == BlsScalar::zero() {
+                    return Witness(w);
+                }
+
+                let constraint = Constraint::new().left(1).a(w).constant(c);
+
+                Witness(composer.gate_add(constraint))
+            }
+            (Witness(a), Witness(b)) => {
+                let constraint = Constraint::new().left(1).a(a).right(1).b(b);
+
+                Witness(composer.gate_add(constraint))
+            }
+        }
+    }
+
+    /// Witness of the input, appending the constant to the circuit if needed
+    fn witness(self, composer: &mut TurboComposer) -> Witness {
+        match self {
+            Self::Witness(w) => w,
+            Self::Constant(c) if c == BlsScalar::zero() => {
+                TurboComposer::constant_zero()
+            }
+            Self::Constant(c) => composer.append_constant(c),
+        }
+    }
+}
+
+/// Apply the permutation to a state of mixed inputs
+///
+/// If every element of the state is constant, the permutation is computed
+/// natively and no gate is appended to the circuit.
+fn permute(composer: &mut TurboComposer, state: &mut [GadgetInput; WIDTH]) {
+    let mut constants = [BlsScalar::zero(); WIDTH];
+    let folded = state
+        .iter()
+        .zip(constants.iter_mut())
+        .all(|(s, c)| match s {
+            GadgetInput::Constant(s) => {
+                *c = *s;
+                true
+            }
+            GadgetInput::Witness(_) => false,
+        });
+
+    if folded {
+        ScalarStrategy::new().perm(&mut constants);
+        state
+            .iter_mut()
+            .zip(constants.iter())
+            .for_each(|(s, c)| *s = GadgetInput::Constant(*c));
+
+        return;
+    }
+
+    let mut witnesses = [TurboComposer::constant_zero(); WIDTH];
+    witnesses
+        .iter_mut()
+        .zip(state.iter())
+        .for_each(|(w, s)| *w = s.witness(composer));
+
+    GadgetStrategy::gadget(composer, &mut witnesses);
+
+    state
+        .iter_mut()
+        .zip(witnesses.iter())
+        .for_each(|(s, w)| *s = GadgetInput::Witness(*w));
+}
+
+/// Mirror the implementation of [`super::hash`] inside of a PLONK circuit for
+/// a message of mixed constants and witnesses.
+///
+/// The result is the same as [`gadget`], but the constant inputs are folded
+/// into the constant term of the addition gates, and the permutations whose
+/// state is entirely constant, such as the ones absorbing a fixed prefix, are
+/// computed natively. The circuit is then smaller than the one of [`gadget`]
+/// with the constants appended as witnesses.
+pub fn gadget_mixed(
+    composer: &mut TurboComposer,
+    messages: &[GadgetInput],
+) -> Witness {
+    gadget_mixed_with_domain(composer, domain::DEFAULT, messages)
+}
+
+/// Mirror the implementation of [`super::hash_with_domain`] inside of a PLONK
+/// circuit for a message of mixed constants and witnesses, as in
+/// [`gadget_mixed`].
+pub fn gadget_mixed_with_domain(
+    composer: &mut TurboComposer,
+    domain: u64,
+    messages: &[GadgetInput],
+) -> Witness {
+    let mut state = [GadgetInput::Constant(BlsScalar::zero()); WIDTH];
+    state[0] = GadgetInput::Constant(domain::capacity(domain));
+
+    if messages.is_empty() && domain == domain::DEFAULT {
+        return TurboComposer::constant_zero();
+    }
+
+    let one = GadgetInput::Constant(BlsScalar::one());
+
+    // The same padding rule as `hash_with_domain`, with the permutation of a
+    // full chunk deferred until the next message
+    let mut pos = 0;
+    messages.iter().for_each(|m| {
+        if pos == WIDTH - 1 {
+            permute(composer, &mut state);
+            pos = 0;
+        }
+
+        state[pos + 1] = state[pos + 1].add(composer, *m);
+        pos += 1;
+    });
+
+    if pos == WIDTH - 1 {
+        permute(composer, &mut state);
+        pos = 0;
+    }
+
+    state[pos + 1] = state[pos + 1].add(composer, one);
+    permute(composer, &mut state);
+
+    state[1].witness(composer)
+}
+
+/// Incremental version of [`gadget`], mirroring [`super::Sponge`] inside of a
+/// PLONK circuit.
+///
+/// The gates appended to the composer are the same, and in the same order, as
+/// the ones of [`gadget`] for the same sequence of messages. The midstate can
+/// be cloned to reuse a common prefix of messages.
+#[derive(Debug, Clone)]
+pub struct SpongeGadget {
+    state: [Witness; WIDTH],
+    pos: usize,
+    domain: u64,
+}
+
+impl Default for SpongeGadget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpongeGadget {
+    /// Create a new sponge gadget with an empty state
+    pub fn new() -> Self {
+        Self {
+            state: [TurboComposer::constant_zero(); WIDTH],
+            pos: 0,
+            domain: domain::DEFAULT,
+        }
+    }
+
+    /// Create a new sponge gadget for the provided domain, as in
+    /// [`gadget_with_domain`]
+    pub fn with_domain(composer: &mut TurboComposer, domain: u64) -> Self {
+        let mut state = [TurboComposer::constant_zero(); WIDTH];
+        state[0] = capacity(composer, domain);
+
+        Self {
+            state,
+            pos: 0,
+            domain,
+        }
+    }
+
+    /// Absorb a single message witness into the sponge
+    pub fn absorb(&mut self, composer: &mut TurboComposer, message: Witness) {
+        if self.pos == WIDTH - 1 {
+            GadgetStrategy::gadget(composer, &mut self.state);
+            self.pos = 0;
+        }
+
+        let s = &mut self.state[self.pos + 1];
+        let constraint = Constraint::new().left(1).a(*s).right(1).b(message);
+
+        *s = composer.gate_add(constraint);
+        self.pos += 1;
+    }
+
+    /// Absorb a slice of message witnesses into the sponge
+    pub fn absorb_slice(
+        &mut self,
+        composer: &mut TurboComposer,
+        messages: &[Witness],
+    ) {
+        messages.iter().for_each(|m| self.absorb(composer, *m));
+    }
+
+    /// Apply the padding to the absorbed messages and return the hash witness
+    ///
+    /// If no message was absorbed in the default domain, the result is the
+    /// constant zero witness.
+    pub fn finalize(mut self, composer: &mut TurboComposer) -> Witness {
+        if self.pos == 0 && self.domain == domain::DEFAULT {
+            return TurboComposer::constant_zero();
+        }
+
+        if self.pos < WIDTH - 1 {
+            let constraint = Constraint::new()
+                .left(1)
+                .a(self.state[self.pos + 1])
+                .constant(1);
+
+            self.state[self.pos + 1] = composer.gate_add(constraint);
+        } else {
+            GadgetStrategy::gadget(composer, &mut self.state);
+
+            let constraint =
+                Constraint::new().left(1).a(self.state[1]).constant(1);
+
+            self.state[1] = composer.gate_add(constraint);
+        }
+
+        GadgetStrategy::gadget(composer, &mut self.state);
+
+        self.state[1]
+    }
+}
+
+/// Mirror the implementation of [`super::hash`] inside of a PLONK circuit for
+/// a message of variable length.
+///
+/// Unlike [`gadget`], the circuit is defined only by the maximum length `MAX`
+/// of the message. The actual length is provided as the witness `len`, and
+/// the result is `hash(&messages[..len])` for any `len <= MAX`. The elements of
+/// `messages` after `len` are ignored.
+///
+/// The padding position is selected inside of the circuit with a one-hot
+/// encoding of `len`, so the circuit will always perform `MAX / (WIDTH - 1) +
+/// 1` permutations. The circuit is not satisfiable if `len > MAX`.
+pub fn gadget_var_len<const MAX: usize>(
+    composer: &mut TurboComposer,
+    messages: &[Witness; MAX],
+    len: Witness,
+) -> Witness {
+    let zero = TurboComposer::constant_zero();
+
+    // Safety: the witness value is only used to compute the selectors, and
+    // these are fully constrained against `len` below
+    let l = unsafe { *composer.evaluate_witness(&len) };
+
+    // One-hot encoding of the length: `e[j] == 1` if and only if `len == j`
+    let mut e = vec![zero; MAX + 1];
+    let mut sum = zero;
+    let mut weighted = zero;
+    e.iter_mut().enumerate().for_each(|(j, e)| {
+        let j = BlsScalar::from(j as u64);
+
+        *e = composer.append_witness(BlsScalar::from(l == j));
+        composer.component_boolean(*e);
+
+        let constraint = Constraint::new().left(1).a(sum).right(1).b(*e);
+        sum = composer.gate_add(constraint);
+
+        let constraint = Constraint::new().left(1).a(weighted).right(j).b(*e);
+        weighted = composer.gate_add(constraint);
+    });
+
+    composer.assert_equal_constant(sum, BlsScalar::one(), None);
+    composer.assert_equal(weighted, len);
+
+    // The padded message stream is `m[i]` for `i < len`, `1` for `i == len`
+    // and `0` afterwards. `c` is set from the padding position onwards.
+    let blocks = MAX / (WIDTH - 1) + 1;
+    let mut stream = vec![zero; blocks * (WIDTH - 1)];
+    let mut c = zero;
+    stream
+        .iter_mut()
+        .enumerate()
+        .take(MAX + 1)
+        .for_each(|(i, p)| {
+            let constraint = Constraint::new().left(1).a(c).right(1).b(e[i]);
+            c = composer.gate_add(constraint);
+
+            *p = match messages.get(i) {
+                // m - c·m + e
+                Some(m) => {
+                    let constraint =
+                        Constraint::new().mult(-BlsScalar::one()).a(c).b(*m);
+                    let m_c = composer.gate_mul(constraint);
+
+                    let constraint = Constraint::new()
+                        .left(1)
+                        .a(*m)
+                        .right(1)
+                        .b(m_c)
+                        .fourth(1)
+                        .d(e[i]);
+                    composer.gate_add(constraint)
+                }
+                None => e[i],
+            };
+        });
+
+    // The result is the first element of the state after the block that
+    // contains the padding, and zero for an empty message
+    let mut state = [zero; WIDTH];
+    let mut output = zero;
+    stream.chunks(WIDTH - 1).enumerate().for_each(|(k, chunk)| {
+        state[1..].iter_mut().zip(chunk.iter()).for_each(|(s, c)| {
+            let constraint = Constraint::new().left(1).a(*s).right(1).b(*c);
+
+            *s = composer.gate_add(constraint);
+        });
+
+        GadgetStrategy::gadget(composer, &mut state);
+
+        let mut f = zero;
+        e.iter()
+            .skip(k * (WIDTH - 1))
+            .take(WIDTH - 1)
+            .for_each(|e| {
+                let constraint = Constraint::new().left(1).a(f).right(1).b(*e);
+                f = composer.gate_add(constraint);
+            });
+
+        let constraint = Constraint::new()
+            .mult(1)
+            .a(f)
+            .b(state[1])
+            .fourth(1)
+            .d(output);
+        output = composer.gate_mul(constraint);
+    });
+
+    let constraint = Constraint::new()
+        .mult(-BlsScalar::one())
+        .a(e[0])
+        .b(output)
+        .fourth(1)
+        .d(output);
+    composer.gate_mul(constraint)
+}
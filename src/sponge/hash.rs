@@ -6,6 +6,8 @@
 
 //! Sponge hash and gadget definition
 
+use super::domain;
+
 use dusk_bls12_381::BlsScalar;
 use dusk_hades::{ScalarStrategy, Strategy, WIDTH};
 
@@ -22,10 +24,35 @@ use dusk_hades::{ScalarStrategy, Strategy, WIDTH};
 /// The last permutation will append `1` to the message as a padding separator
 /// value. The padding values will be zeroes. To avoid collision, the padding
 /// will imply one additional permutation in case `|m|` is a multiple of `r`.
+///
+/// This is the same as [`hash_with_domain`] for [`domain::DEFAULT`].
 pub fn hash(messages: &[BlsScalar]) -> BlsScalar {
+    hash_with_domain(domain::DEFAULT, messages)
+}
+
+/// Hash the `messages` with the `domain` tag mixed into the capacity element.
+///
+/// The padding rules are the same as [`hash`]. For backwards compatibility,
+/// an empty message hashes to zero in the [`domain::DEFAULT`] domain. In any
+/// other domain, the padding is applied to the empty message as well, so the
+/// result is different for every domain.
+pub fn hash_with_domain(domain: u64, messages: &[BlsScalar]) -> BlsScalar {
     let mut h = ScalarStrategy::new();
     let mut state = [BlsScalar::zero(); WIDTH];
 
+    state[0] = domain::capacity(domain);
+
+    if messages.is_empty() {
+        if domain == domain::DEFAULT {
+            return BlsScalar::zero();
+        }
+
+        state[1] += BlsScalar::one();
+        h.perm(&mut state);
+
+        return state[1];
+    }
+
     // If exists an `m` such as `m · (WIDTH - 1) == l`, then the last iteration
     // index should be `m - 1`.
     //
@@ -67,3 +94,83 @@ pub fn hash(messages: &[BlsScalar]) -> BlsScalar {
 
     state[1]
 }
+
+/// Incremental version of [`hash`].
+///
+/// Messages are absorbed one at a time, and the permutation of a full chunk is
+/// deferred until the next message arrives, so [`Sponge::finalize`] can apply
+/// the same padding rule as [`hash`]. The output of the sponge is identical to
+/// [`hash`] for the same sequence of messages.
+///
+/// The sponge is `Clone`, so a common prefix can be absorbed once and the
+/// resulting midstate reused for several inputs.
+#[derive(Debug, Clone)]
+pub struct Sponge {
+    state: [BlsScalar; WIDTH],
+    pos: usize,
+}
+
+impl Default for Sponge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sponge {
+    /// Create a new sponge with an empty state
+    pub const fn new() -> Self {
+        Self::with_domain(domain::DEFAULT)
+    }
+
+    /// Create a new sponge for the provided domain, as in [`hash_with_domain`]
+    pub const fn with_domain(domain: u64) -> Self {
+        let mut state = [BlsScalar::zero(); WIDTH];
+        state[0] = domain::capacity(domain);
+
+        Self { state, pos: 0 }
+    }
+
+    /// Absorb a single message into the sponge
+    pub fn absorb(&mut self, message: &BlsScalar) {
+        // The previous chunk is full, so it is safe to permute it now that
+        // there is at least one more message
+        if self.pos == WIDTH - 1 {
+            ScalarStrategy::new().perm(&mut self.state);
+            self.pos = 0;
+        }
+
+        self.state[self.pos + 1] += message;
+        self.pos += 1;
+    }
+
+    /// Absorb a slice of messages into the sponge
+    pub fn absorb_slice(&mut self, messages: &[BlsScalar]) {
+        messages.iter().for_each(|m| self.absorb(m));
+    }
+
+    /// Apply the padding to the absorbed messages and return the hash
+    ///
+    /// If no message was absorbed in the default domain, the result is zero,
+    /// as in [`hash`].
+    pub fn finalize(mut self) -> BlsScalar {
+        let mut h = ScalarStrategy::new();
+
+        // `pos` is only zero if nothing was absorbed, and in that case the
+        // capacity element still holds the domain
+        if self.pos == 0 && self.state[0] == BlsScalar::zero() {
+            return BlsScalar::zero();
+        }
+
+        if self.pos < WIDTH - 1 {
+            self.state[self.pos + 1] += BlsScalar::one();
+        } else {
+            h.perm(&mut self.state);
+
+            self.state[1] += BlsScalar::one();
+        }
+
+        h.perm(&mut self.state);
+
+        self.state[1]
+    }
+}
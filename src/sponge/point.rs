@@ -0,0 +1,226 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Hash to a point of the prime order subgroup of JubJub
+//!
+//! The messages are hashed with [`sponge::hash`] into `h`, and the candidate
+//! `x = h + i` is tried for `i = 0, 1, ..` until `u = (1 + x²) / (1 - d·x²)`
+//! is a square. The point `(x, y)` is on the curve for `y = √u`, and the root
+//! with `y <= (p - 1) / 2` is selected. The result is this point multiplied by
+//! the cofactor.
+//!
+//! Since `d` is not a square, `1 - d·x²` is never zero, and every candidate
+//! has a probability of one half of being accepted.
+
+use crate::sponge;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_jubjub::{JubJubAffine, JubJubExtended, EDWARDS_D};
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use dusk_plonk::prelude::*;
+
+/// Maximum number of candidates tried by [`gadget`]
+///
+/// The probability of [`hash`] requiring more candidates is `2^-64`.
+pub const MAX_ATTEMPTS: usize = 64;
+
+/// Quadratic non-residue used to certify the rejected candidates
+#[cfg(feature = "alloc")]
+const NON_RESIDUE: u64 = 7;
+
+/// Returns the numerator and denominator of `y²` for the candidate `x`
+fn candidate(x: &BlsScalar) -> (BlsScalar, BlsScalar) {
+    let x2 = x.square();
+
+    (BlsScalar::one() + x2, BlsScalar::one() - EDWARDS_D * x2)
+}
+
+/// Returns the square root of `u` that is smaller than `(p - 1) / 2`, if any
+fn sqrt(u: &BlsScalar) -> Option<BlsScalar> {
+    Option::<BlsScalar>::from(u.sqrt()).map(|y| {
+        let (a, b) = (y.to_bytes(), (-y).to_bytes());
+
+        if a.iter().rev().le(b.iter().rev()) {
+            y
+        } else {
+            -y
+        }
+    })
+}
+
+/// Returns the index of the accepted candidate and the point before the
+/// cofactor multiplication
+fn attempt(h: &BlsScalar) -> (u64, JubJubAffine) {
+    (0u64..)
+        .find_map(|i| {
+            let x = *h + BlsScalar::from(i);
+            let (num, den) = candidate(&x);
+
+            // The denominator is never zero
+            let u = num * den.invert().unwrap();
+
+            sqrt(&u).map(|y| (i, JubJubAffine::from_raw_unchecked(x, y)))
+        })
+        .expect("The candidates are unbounded")
+}
+
+/// Hash the messages to a point of the prime order subgroup of JubJub
+pub fn hash(messages: &[BlsScalar]) -> JubJubAffine {
+    let (_, p) = attempt(&sponge::hash(messages));
+
+    JubJubExtended::from(p).mul_by_cofactor().into()
+}
+
+/// Mirror the implementation of [`hash`] inside of a PLONK circuit.
+///
+/// Every one of the [`MAX_ATTEMPTS`] candidates is evaluated in the circuit.
+/// The accepted candidate is selected with a one-hot encoding. Its `y` is
+/// constrained to be the smaller square root, and every candidate before it
+/// is certified not to be a square, so the prover cannot select a different
+/// point. The circuit is not satisfiable if more than [`MAX_ATTEMPTS`]
+/// candidates are required.
+///
+/// The cofactor is cleared with three point doublings.
+#[cfg(feature = "alloc")]
+pub fn gadget(
+    composer: &mut TurboComposer,
+    messages: &[Witness],
+) -> WitnessPoint {
+    let zero = TurboComposer::constant_zero();
+    let h = sponge::gadget(composer, messages);
+
+    // Safety: the witness value is only used to compute the candidate
+    // selection and the square roots, that are fully constrained below
+    let h_native = unsafe { *composer.evaluate_witness(&h) };
+    let (accepted, p) = attempt(&h_native);
+    let non_residue = BlsScalar::from(NON_RESIDUE);
+
+    let mut selected = vec![zero; MAX_ATTEMPTS];
+    let mut sum = zero;
+    let mut index = zero;
+    let mut y = zero;
+
+    // `prefix` is zero before the accepted candidate, and one afterwards
+    let mut prefix = zero;
+
+    selected.iter_mut().enumerate().for_each(|(i, e)| {
+        let j = BlsScalar::from(i as u64);
+
+        let x = h_native + j;
+        let (num_native, den_native) = candidate(&x);
+
+        // Square root of `u` for the accepted candidate, and of
+        // `NON_RESIDUE · u` for the rejected ones before it
+        let r = match (i as u64).cmp(&accepted) {
+            core::cmp::Ordering::Less => {
+                let u = non_residue * num_native * den_native.invert().unwrap();
+                sqrt(&u).unwrap_or_default()
+            }
+            core::cmp::Ordering::Equal => p.get_y(),
+            core::cmp::Ordering::Greater => BlsScalar::zero(),
+        };
+
+        *e = composer.append_witness(BlsScalar::from(i as u64 == accepted));
+        composer.component_boolean(*e);
+
+        let constraint = Constraint::new().left(1).a(sum).right(1).b(*e);
+        sum = composer.gate_add(constraint);
+
+        let constraint = Constraint::new().left(1).a(index).right(j).b(*e);
+        index = composer.gate_add(constraint);
+
+        // The constraint is active for the candidates up to the accepted one
+        let constraint = Constraint::new()
+            .left(-BlsScalar::one())
+            .a(prefix)
+            .constant(1);
+        let active = composer.gate_add(constraint);
+
+        let constraint = Constraint::new().left(1).a(prefix).right(1).b(*e);
+        prefix = composer.gate_add(constraint);
+
+        // `rejected` is one before the accepted candidate
+        let constraint = Constraint::new()
+            .left(-BlsScalar::one())
+            .a(prefix)
+            .constant(1);
+        let rejected = composer.gate_add(constraint);
+
+        let constraint = Constraint::new().left(1).a(h).constant(j);
+        let x = composer.gate_add(constraint);
+
+        let constraint = Constraint::new().mult(1).a(x).b(x);
+        let x2 = composer.gate_mul(constraint);
+
+        let constraint = Constraint::new().left(1).a(x2).constant(1);
+        let num = composer.gate_add(constraint);
+
+        let constraint = Constraint::new().left(-EDWARDS_D).a(x2).constant(1);
+        let den = composer.gate_add(constraint);
+
+        let r = composer.append_witness(r);
+
+        let constraint = Constraint::new().mult(1).a(r).b(r);
+        let r2 = composer.gate_mul(constraint);
+
+        let constraint = Constraint::new().mult(1).a(r2).b(den);
+        let lhs = composer.gate_mul(constraint);
+
+        // num · (1 + (NON_RESIDUE - 1) · rejected)
+        let constraint = Constraint::new()
+            .mult(non_residue - BlsScalar::one())
+            .a(num)
+            .b(rejected)
+            .fourth(1)
+            .d(num);
+        let rhs = composer.gate_mul(constraint);
+
+        let constraint = Constraint::new()
+            .left(1)
+            .a(lhs)
+            .right(-BlsScalar::one())
+            .b(rhs);
+        let diff = composer.gate_add(constraint);
+
+        let constraint = Constraint::new().mult(1).a(diff).b(active);
+        let diff = composer.gate_mul(constraint);
+        composer.assert_equal_constant(diff, BlsScalar::zero(), None);
+
+        let constraint = Constraint::new().mult(1).a(*e).b(r).fourth(1).d(y);
+        y = composer.gate_mul(constraint);
+    });
+
+    composer.assert_equal_constant(sum, BlsScalar::one(), None);
+
+    // `y <= (p - 1) / 2` if and only if both `y` and `(p - 1) / 2 - y` are
+    // smaller than `2^254`
+    let half = -BlsScalar::from(2u64).invert().unwrap();
+
+    let constraint = Constraint::new()
+        .left(-BlsScalar::one())
+        .a(y)
+        .constant(half);
+    let y_complement = composer.gate_add(constraint);
+
+    composer.component_range(y, 254);
+    composer.component_range(y_complement, 254);
+
+    let constraint = Constraint::new().left(1).a(h).right(1).b(index);
+    let x = composer.gate_add(constraint);
+
+    let point = composer.append_point(p);
+    composer.assert_equal(*point.x(), x);
+    composer.assert_equal(*point.y(), y);
+
+    // Clear the cofactor
+    let point = composer.component_add_point(point, point);
+    let point = composer.component_add_point(point, point);
+    composer.component_add_point(point, point)
+}
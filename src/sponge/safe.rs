@@ -0,0 +1,340 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Sponge API for Field Elements (SAFE)
+//!
+//! The sponge is initialized with an IO pattern, that is the sequence of
+//! absorb and squeeze calls the protocol is going to perform. The pattern is
+//! hashed into a tag that is used as the capacity element, so sponges used
+//! with different call shapes will never produce colliding outputs.
+//!
+//! Every call of the pattern must process between `1` and `2^31 - 1`
+//! elements, otherwise the sponge can't be started.
+//!
+//! Every call must match the next element of the declared pattern, and the
+//! whole pattern must be consumed by [`SafeSponge::finish`]. Otherwise, the
+//! call fails with [`Error::IOPatternViolation`], the state is erased, and
+//! every subsequent call will fail.
+
+use crate::sponge::Sponge;
+use crate::Error;
+
+use core::convert::TryFrom;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_hades::{ScalarStrategy, Strategy, WIDTH};
+
+#[cfg(feature = "alloc")]
+use dusk_hades::GadgetStrategy;
+#[cfg(feature = "alloc")]
+use dusk_plonk::prelude::*;
+
+/// Number of elements absorbed or squeezed per permutation
+const RATE: usize = WIDTH - 1;
+
+/// Flag set in the encoding of absorb calls
+const ABSORB_FLAG: u64 = 0x8000_0000;
+
+/// A single call of an IO pattern, with the number of elements it processes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Call {
+    /// Absorb the given number of elements
+    Absorb(u32),
+    /// Squeeze the given number of elements
+    Squeeze(u32),
+}
+
+impl Call {
+    /// Encode the call as a word, failing if its length is zero or doesn't
+    /// fit below [`ABSORB_FLAG`]
+    fn encode(&self) -> Result<u64, Error> {
+        let (flag, n) = match self {
+            Call::Absorb(n) => (ABSORB_FLAG, *n as u64),
+            Call::Squeeze(n) => (0, *n as u64),
+        };
+
+        if n == 0 || n >= ABSORB_FLAG {
+            return Err(Error::IOPatternViolation);
+        }
+
+        Ok(flag + n)
+    }
+
+    /// Merge a call into the previous one, if they are of the same kind
+    fn merge(&self, call: &Call) -> Option<Result<Call, Error>> {
+        let merged = match (self, call) {
+            (Call::Absorb(a), Call::Absorb(b)) => {
+                a.checked_add(*b).map(Call::Absorb)
+            }
+            (Call::Squeeze(a), Call::Squeeze(b)) => {
+                a.checked_add(*b).map(Call::Squeeze)
+            }
+            _ => return None,
+        };
+
+        Some(merged.ok_or(Error::IOPatternViolation))
+    }
+}
+
+/// Compute the tag of an IO pattern
+///
+/// Consecutive calls of the same kind are aggregated, so `[Absorb(1),
+/// Absorb(2)]` has the same tag as `[Absorb(3)]`. Absorb calls are encoded as
+/// `2^31 + n` and squeeze calls as `n`, and the resulting words are hashed with
+/// [`super::hash`].
+///
+/// Will return [`Error::IOPatternViolation`] if a call, before or after the
+/// aggregation, has a length of zero or of at least `2^31`.
+pub fn tag(pattern: &[Call]) -> Result<BlsScalar, Error> {
+    let mut sponge = Sponge::new();
+    let mut current: Option<Call> = None;
+
+    for call in pattern {
+        // Every call is checked on its own, so a zero length can't be hidden
+        // by the aggregation
+        call.encode()?;
+
+        current = match current.as_ref().and_then(|c| c.merge(call)) {
+            Some(merged) => Some(merged?),
+            None => {
+                if let Some(c) = current {
+                    sponge.absorb(&BlsScalar::from(c.encode()?));
+                }
+
+                Some(*call)
+            }
+        };
+    }
+
+    if let Some(c) = current {
+        sponge.absorb(&BlsScalar::from(c.encode()?));
+    }
+
+    Ok(sponge.finalize())
+}
+
+/// Length of a call processing `len` elements
+///
+/// A length that doesn't fit in a [`Call`] is mapped to zero, that never
+/// matches a call of a valid pattern.
+fn call_len(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or(0)
+}
+
+/// Sponge following the SAFE design, using the `Hades` ScalarStrategy.
+#[derive(Debug, Clone)]
+pub struct SafeSponge<'a> {
+    state: [BlsScalar; WIDTH],
+    pattern: &'a [Call],
+    absorb_pos: usize,
+    squeeze_pos: usize,
+}
+
+impl<'a> SafeSponge<'a> {
+    /// Start a new sponge for the provided IO pattern
+    ///
+    /// Will return an error if the tag of the pattern can't be computed.
+    pub fn start(pattern: &'a [Call]) -> Result<Self, Error> {
+        let mut state = [BlsScalar::zero(); WIDTH];
+        state[0] = tag(pattern)?;
+
+        Ok(Self {
+            state,
+            pattern,
+            absorb_pos: 0,
+            // Force a permutation before squeezing from the initial state
+            squeeze_pos: RATE,
+        })
+    }
+
+    fn next_call(&mut self, call: Call) -> Result<(), Error> {
+        match self.pattern.split_first() {
+            Some((c, pattern)) if *c == call => {
+                self.pattern = pattern;
+                Ok(())
+            }
+            _ => Err(self.abort()),
+        }
+    }
+
+    fn abort(&mut self) -> Error {
+        self.state = [BlsScalar::zero(); WIDTH];
+        self.pattern = &[];
+
+        Error::IOPatternViolation
+    }
+
+    /// Absorb `input` into the sponge
+    ///
+    /// The next call of the pattern must be `Absorb(input.len())`.
+    pub fn absorb(&mut self, input: &[BlsScalar]) -> Result<(), Error> {
+        self.next_call(Call::Absorb(call_len(input.len())))?;
+
+        let mut h = ScalarStrategy::new();
+        input.iter().for_each(|x| {
+            if self.absorb_pos == RATE {
+                h.perm(&mut self.state);
+                self.absorb_pos = 0;
+            }
+
+            self.state[self.absorb_pos + 1] += x;
+            self.absorb_pos += 1;
+        });
+
+        // The next squeeze must permute the absorbed elements
+        self.squeeze_pos = RATE;
+
+        Ok(())
+    }
+
+    /// Squeeze `output.len()` elements from the sponge
+    ///
+    /// The next call of the pattern must be `Squeeze(output.len())`.
+    pub fn squeeze(&mut self, output: &mut [BlsScalar]) -> Result<(), Error> {
+        self.next_call(Call::Squeeze(call_len(output.len())))?;
+
+        let mut h = ScalarStrategy::new();
+        output.iter_mut().for_each(|o| {
+            if self.squeeze_pos == RATE {
+                h.perm(&mut self.state);
+                self.squeeze_pos = 0;
+                self.absorb_pos = 0;
+            }
+
+            *o = self.state[self.squeeze_pos + 1];
+            self.squeeze_pos += 1;
+        });
+
+        Ok(())
+    }
+
+    /// Finish the sponge, checking that the whole IO pattern was used
+    pub fn finish(mut self) -> Result<(), Error> {
+        let finished = self.pattern.is_empty();
+        let err = self.abort();
+
+        if finished {
+            Ok(())
+        } else {
+            Err(err)
+        }
+    }
+}
+
+/// Mirror the implementation of [`SafeSponge`] inside of a PLONK circuit.
+///
+/// The circuit will be defined by the IO pattern, and the tag is appended as
+/// a circuit constant.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct SafeSpongeGadget<'a> {
+    state: [Witness; WIDTH],
+    pattern: &'a [Call],
+    absorb_pos: usize,
+    squeeze_pos: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> SafeSpongeGadget<'a> {
+    /// Start a new sponge gadget for the provided IO pattern
+    ///
+    /// Will return an error if the tag of the pattern can't be computed.
+    pub fn start(
+        composer: &mut TurboComposer,
+        pattern: &'a [Call],
+    ) -> Result<Self, Error> {
+        let mut state = [TurboComposer::constant_zero(); WIDTH];
+        state[0] = composer.append_constant(tag(pattern)?);
+
+        Ok(Self {
+            state,
+            pattern,
+            absorb_pos: 0,
+            squeeze_pos: RATE,
+        })
+    }
+
+    fn next_call(&mut self, call: Call) -> Result<(), Error> {
+        match self.pattern.split_first() {
+            Some((c, pattern)) if *c == call => {
+                self.pattern = pattern;
+                Ok(())
+            }
+            _ => Err(self.abort()),
+        }
+    }
+
+    fn abort(&mut self) -> Error {
+        self.state = [TurboComposer::constant_zero(); WIDTH];
+        self.pattern = &[];
+
+        Error::IOPatternViolation
+    }
+
+    /// Absorb `input` into the sponge
+    ///
+    /// The next call of the pattern must be `Absorb(input.len())`.
+    pub fn absorb(
+        &mut self,
+        composer: &mut TurboComposer,
+        input: &[Witness],
+    ) -> Result<(), Error> {
+        self.next_call(Call::Absorb(call_len(input.len())))?;
+
+        input.iter().for_each(|x| {
+            if self.absorb_pos == RATE {
+                GadgetStrategy::gadget(composer, &mut self.state);
+                self.absorb_pos = 0;
+            }
+
+            let s = &mut self.state[self.absorb_pos + 1];
+            let constraint = Constraint::new().left(1).a(*s).right(1).b(*x);
+
+            *s = composer.gate_add(constraint);
+            self.absorb_pos += 1;
+        });
+
+        self.squeeze_pos = RATE;
+
+        Ok(())
+    }
+
+    /// Squeeze `output.len()` witnesses from the sponge
+    ///
+    /// The next call of the pattern must be `Squeeze(output.len())`.
+    pub fn squeeze(
+        &mut self,
+        composer: &mut TurboComposer,
+        output: &mut [Witness],
+    ) -> Result<(), Error> {
+        self.next_call(Call::Squeeze(call_len(output.len())))?;
+
+        output.iter_mut().for_each(|o| {
+            if self.squeeze_pos == RATE {
+                GadgetStrategy::gadget(composer, &mut self.state);
+                self.squeeze_pos = 0;
+                self.absorb_pos = 0;
+            }
+
+            *o = self.state[self.squeeze_pos + 1];
+            self.squeeze_pos += 1;
+        });
+
+        Ok(())
+    }
+
+    /// Finish the sponge, checking that the whole IO pattern was used
+    pub fn finish(mut self) -> Result<(), Error> {
+        let finished = self.pattern.is_empty();
+        let err = self.abort();
+
+        if finished {
+            Ok(())
+        } else {
+            Err(err)
+        }
+    }
+}
@@ -8,9 +8,13 @@
 
 use crate::sponge;
 use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
 
 use dusk_plonk::prelude::*;
 
+#[cfg(feature = "alloc")]
+use core::marker::PhantomData;
+
 /// The constant represents the bitmask used to truncate the hashing results of a sponge application
 /// so that they fit inside of a [`dusk_jubjub::JubJubScalar`] and it's equal to `2^250 - 1`.
 ///
@@ -31,15 +35,101 @@ const TRUNCATION_LIMIT: BlsScalar = BlsScalar([
     0xa62ffba6a1323be,
 ]);
 
-/// Applies [`sponge_hash`] to the `messages` recieved truncating the result to make it fit
+/// Types a sponge hash can be truncated to
+///
+/// The truncation keeps the [`Truncation::BITS`] least significant bits of the
+/// canonical representation of the hash. The number of bits must be even so
+/// the truncation can be performed by the logic gates of PLONK, and
+/// [`gadget_bits`] will fail to compile for an odd number of bits.
+///
+/// ```compile_fail
+/// use dusk_bls12_381::BlsScalar;
+/// use dusk_plonk::prelude::*;
+/// use dusk_poseidon::sponge::truncated::{self, Truncation};
+///
+/// struct Odd(BlsScalar);
+///
+/// impl Truncation for Odd {
+///     const BITS: usize = 63;
+///
+///     fn truncate(scalar: &BlsScalar) -> Self {
+///         Odd(*scalar)
+///     }
+///
+///     fn to_scalar(&self) -> BlsScalar {
+///         self.0
+///     }
+/// }
+///
+/// let gadget: fn(&mut TurboComposer, &[Witness]) -> Witness =
+///     truncated::gadget_bits::<Odd>;
+/// ```
+pub trait Truncation: Sized {
+    /// Number of bits kept by the truncation
+    const BITS: usize;
+
+    /// Truncate the scalar to its [`Truncation::BITS`] least significant bits
+    fn truncate(scalar: &BlsScalar) -> Self;
+
+    /// Return the truncated value as a scalar
+    fn to_scalar(&self) -> BlsScalar;
+}
+
+impl Truncation for u64 {
+    const BITS: usize = 64;
+
+    fn truncate(scalar: &BlsScalar) -> Self {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&scalar.to_bytes()[..8]);
+
+        u64::from_le_bytes(bytes)
+    }
+
+    fn to_scalar(&self) -> BlsScalar {
+        BlsScalar::from(*self)
+    }
+}
+
+impl Truncation for u128 {
+    const BITS: usize = 128;
+
+    fn truncate(scalar: &BlsScalar) -> Self {
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&scalar.to_bytes()[..16]);
+
+        u128::from_le_bytes(bytes)
+    }
+
+    fn to_scalar(&self) -> BlsScalar {
+        BlsScalar::from_raw([*self as u64, (*self >> 64) as u64, 0, 0])
+    }
+}
+
+impl Truncation for JubJubScalar {
+    const BITS: usize = 250;
+
+    fn truncate(scalar: &BlsScalar) -> Self {
+        JubJubScalar::from_raw((*scalar & TRUNCATION_LIMIT).reduce().0)
+    }
+
+    fn to_scalar(&self) -> BlsScalar {
+        (*self).into()
+    }
+}
+
+/// Applies [`sponge::hash`] to the `messages` recieved truncating the result to make it fit
 /// inside a `JubJubScalar.`
 pub fn hash(messages: &[BlsScalar]) -> JubJubScalar {
-    JubJubScalar::from_raw(
-        (sponge::hash(messages) & TRUNCATION_LIMIT).reduce().0,
-    )
+    hash_bits(messages)
+}
+
+/// Applies [`sponge::hash`] to the `messages` and truncates the result to
+/// [`Truncation::BITS`] bits.
+pub fn hash_bits<T: Truncation>(messages: &[BlsScalar]) -> T {
+    T::truncate(&sponge::hash(messages))
 }
 
-/// Mirror the implementation of [`truncate_hash`] inside of a PLONK circuit.
+/// Mirror the implementation of [`hash`] inside of a PLONK circuit.
 ///
 /// The circuit will be defined by the length of `messages`. This means that a
 /// pre-computed circuit will not behave generically for different messages
@@ -53,9 +143,57 @@ pub fn hash(messages: &[BlsScalar]) -> JubJubScalar {
 /// to fit inside of a [`JubJubScalar`].
 #[cfg(feature = "alloc")]
 pub fn gadget(composer: &mut TurboComposer, message: &[Witness]) -> Witness {
+    gadget_bits::<JubJubScalar>(composer, message)
+}
+
+/// Compile-time check that the [`Truncation::BITS`] of `T` are even
+///
+/// The check lives outside of the trait, so implementors can't override it.
+#[cfg(feature = "alloc")]
+struct EvenBits<T>(PhantomData<T>);
+
+#[cfg(feature = "alloc")]
+impl<T: Truncation> EvenBits<T> {
+    const CHECK: () =
+        assert!(T::BITS % 2 == 0, "The truncation bits must be even");
+}
+
+/// Mirror the implementation of [`hash_bits`] inside of a PLONK circuit.
+///
+/// The returned witness holds the [`Truncation::to_scalar`] representation of
+/// the truncated hash.
+#[cfg(feature = "alloc")]
+pub fn gadget_bits<T: Truncation>(
+    composer: &mut TurboComposer,
+    message: &[Witness],
+) -> Witness {
+    #[allow(clippy::let_unit_value)]
+    let _: () = EvenBits::<T>::CHECK;
+
     let zero = TurboComposer::constant_zero();
     let h = sponge::gadget(composer, message);
 
-    // Truncate to 250 bits
-    composer.component_xor(h, zero, 250)
+    // Truncate to `T::BITS` bits
+    composer.component_xor(h, zero, T::BITS)
+}
+
+/// Number of gates appended to the circuit by [`gadget`] for a message of `n`
+/// witnesses
+#[cfg(feature = "alloc")]
+pub const fn gadget_gates(n: usize) -> usize {
+    sponge::gadget_gates(n) + truncation_gates(JubJubScalar::BITS)
+}
+
+/// Number of gates appended to the circuit by [`gadget_bits`] for a message of
+/// `n` witnesses
+#[cfg(feature = "alloc")]
+pub fn gadget_bits_gates<T: Truncation>(n: usize) -> usize {
+    sponge::gadget_gates(n) + truncation_gates(T::BITS)
+}
+
+/// The truncation takes a gate for every two bits kept, rounded up to an even
+/// number, and a final gate.
+#[cfg(feature = "alloc")]
+const fn truncation_gates(bits: usize) -> usize {
+    (bits + bits % 2) / 2 + 1
 }
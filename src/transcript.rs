@@ -0,0 +1,124 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! # Dusk-Poseidon Transcript
+//!
+//! Fiat-Shamir transcript built on a duplex over the Hades permutation.
+//!
+//! The capacity element holds the [`domain::TRANSCRIPT`] tag. Every message is
+//! preceded by its label, and the rate is filled one scalar at a time,
+//! permuting the state whenever it is full. A challenge absorbs its label, a
+//! padding `1` and permutes the state, so the challenge is the first element
+//! of the rate. Following messages are absorbed on top of the squeezed state.
+//!
+//! Labels are byte strings encoded as their [`sponge::hash_bytes`]. They are
+//! part of the protocol description, so the gadget appends them as circuit
+//! constants.
+//!
+//! [`TranscriptGadget`] appends the same operations to a PLONK circuit, and
+//! produces the same challenges as [`Transcript`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use dusk_bls12_381::BlsScalar;
+//! use dusk_jubjub::{JubJubAffine, GENERATOR};
+//! use dusk_poseidon::transcript::Transcript;
+//!
+//! let mut transcript = Transcript::new(b"protocol");
+//!
+//! transcript.append_scalar(b"value", &BlsScalar::from(42u64));
+//! transcript.append_point(b"point", &GENERATOR);
+//!
+//! let c = transcript.challenge_scalar(b"c");
+//! let e = transcript.challenge_jubjub(b"e");
+//! ```
+
+use crate::sponge::{self, domain, truncated::Truncation};
+
+use dusk_bls12_381::BlsScalar;
+use dusk_hades::{ScalarStrategy, Strategy, WIDTH};
+use dusk_jubjub::{JubJubAffine, JubJubScalar};
+
+/// Encode a label as a scalar
+pub(crate) fn label(label: &[u8]) -> BlsScalar {
+    sponge::hash_bytes(label)
+}
+
+/// Fiat-Shamir transcript using the Hades `ScalarStrategy`
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    state: [BlsScalar; WIDTH],
+    pos: usize,
+}
+
+impl Transcript {
+    /// Create a new transcript for the protocol identified by `label`
+    pub fn new(label: &[u8]) -> Self {
+        let mut state = [BlsScalar::zero(); WIDTH];
+        state[0] = domain::capacity(domain::TRANSCRIPT);
+
+        let mut transcript = Self { state, pos: 0 };
+        transcript.absorb(&self::label(label));
+
+        transcript
+    }
+
+    fn absorb(&mut self, scalar: &BlsScalar) {
+        if self.pos == WIDTH - 1 {
+            ScalarStrategy::new().perm(&mut self.state);
+            self.pos = 0;
+        }
+
+        self.state[self.pos + 1] += scalar;
+        self.pos += 1;
+    }
+
+    /// Append a labelled scalar to the transcript
+    pub fn append_scalar(&mut self, label: &[u8], scalar: &BlsScalar) {
+        self.absorb(&self::label(label));
+        self.absorb(scalar);
+    }
+
+    /// Append a labelled point to the transcript
+    ///
+    /// The point is absorbed as its affine coordinates.
+    pub fn append_point(&mut self, label: &[u8], point: &JubJubAffine) {
+        self.absorb(&self::label(label));
+        self.absorb(&point.get_x());
+        self.absorb(&point.get_y());
+    }
+
+    /// Squeeze a labelled challenge from the transcript
+    pub fn challenge_scalar(&mut self, label: &[u8]) -> BlsScalar {
+        let mut h = ScalarStrategy::new();
+
+        self.absorb(&self::label(label));
+
+        if self.pos == WIDTH - 1 {
+            h.perm(&mut self.state);
+            self.pos = 0;
+        }
+
+        self.state[self.pos + 1] += BlsScalar::one();
+        h.perm(&mut self.state);
+        self.pos = 0;
+
+        self.state[1]
+    }
+
+    /// Squeeze a labelled challenge from the transcript, truncated to fit
+    /// inside of a [`JubJubScalar`]
+    pub fn challenge_jubjub(&mut self, label: &[u8]) -> JubJubScalar {
+        JubJubScalar::truncate(&self.challenge_scalar(label))
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod zk;
+
+#[cfg(feature = "alloc")]
+pub use zk::TranscriptGadget;
@@ -0,0 +1,122 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::sponge::{domain, truncated::Truncation};
+
+use dusk_hades::{GadgetStrategy, WIDTH};
+use dusk_plonk::prelude::*;
+
+/// Mirror the implementation of [`super::Transcript`] inside of a PLONK
+/// circuit, using the Hades `GadgetStrategy`.
+///
+/// Labels are added to the state as the constant term of the absorbing gate.
+#[derive(Debug, Clone)]
+pub struct TranscriptGadget {
+    state: [Witness; WIDTH],
+    pos: usize,
+}
+
+impl TranscriptGadget {
+    /// Create a new transcript gadget for the protocol identified by `label`
+    pub fn new(composer: &mut TurboComposer, label: &[u8]) -> Self {
+        let mut state = [TurboComposer::constant_zero(); WIDTH];
+        state[0] =
+            composer.append_constant(domain::capacity(domain::TRANSCRIPT));
+
+        let mut transcript = Self { state, pos: 0 };
+        transcript.absorb_label(composer, label);
+
+        transcript
+    }
+
+    fn next(&mut self, composer: &mut TurboComposer) -> usize {
+        if self.pos == WIDTH - 1 {
+            GadgetStrategy::gadget(composer, &mut self.state);
+            self.pos = 0;
+        }
+
+        self.pos += 1;
+        self.pos
+    }
+
+    fn absorb_label(&mut self, composer: &mut TurboComposer, label: &[u8]) {
+        let i = self.next(composer);
+        let constraint = Constraint::new()
+            .left(1)
+            .a(self.state[i])
+            .constant(super::label(label));
+
+        self.state[i] = composer.gate_add(constraint);
+    }
+
+    fn absorb(&mut self, composer: &mut TurboComposer, witness: Witness) {
+        let i = self.next(composer);
+        let constraint = Constraint::new()
+            .left(1)
+            .a(self.state[i])
+            .right(1)
+            .b(witness);
+
+        self.state[i] = composer.gate_add(constraint);
+    }
+
+    /// Append a labelled scalar witness to the transcript
+    pub fn append_scalar(
+        &mut self,
+        composer: &mut TurboComposer,
+        label: &[u8],
+        scalar: Witness,
+    ) {
+        self.absorb_label(composer, label);
+        self.absorb(composer, scalar);
+    }
+
+    /// Append a labelled point witness to the transcript
+    pub fn append_point(
+        &mut self,
+        composer: &mut TurboComposer,
+        label: &[u8],
+        point: &WitnessPoint,
+    ) {
+        self.absorb_label(composer, label);
+        self.absorb(composer, *point.x());
+        self.absorb(composer, *point.y());
+    }
+
+    /// Squeeze a labelled challenge from the transcript
+    pub fn challenge_scalar(
+        &mut self,
+        composer: &mut TurboComposer,
+        label: &[u8],
+    ) -> Witness {
+        self.absorb_label(composer, label);
+
+        let i = self.next(composer);
+        let constraint = Constraint::new().left(1).a(self.state[i]).constant(1);
+
+        self.state[i] = composer.gate_add(constraint);
+
+        GadgetStrategy::gadget(composer, &mut self.state);
+        self.pos = 0;
+
+        self.state[1]
+    }
+
+    /// Squeeze a labelled challenge from the transcript, truncated to fit
+    /// inside of a [`JubJubScalar`]
+    ///
+    /// The returned witness holds the truncated challenge as a scalar.
+    pub fn challenge_jubjub(
+        &mut self,
+        composer: &mut TurboComposer,
+        label: &[u8],
+    ) -> Witness {
+        let zero = TurboComposer::constant_zero();
+        let c = self.challenge_scalar(composer, label);
+
+        composer.component_xor(c, zero, JubJubScalar::BITS)
+    }
+}
@@ -16,7 +16,7 @@ pub use annotation::{
 };
 pub use branch::{PoseidonBranch, PoseidonLevel};
 pub use leaf::PoseidonLeaf;
-pub use zk::merkle_opening;
+pub use zk::{merkle_opening, merkle_opening_gates};
 
 use crate::Error;
 use canonical::CanonError;
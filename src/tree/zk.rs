@@ -5,7 +5,8 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use super::PoseidonBranch;
-use dusk_hades::GadgetStrategy;
+use crate::sponge::PERMUTATION_GATES;
+use dusk_hades::{GadgetStrategy, WIDTH};
 
 use dusk_plonk::prelude::*;
 
@@ -77,3 +78,15 @@ pub fn merkle_opening<const DEPTH: usize>(
 
     root
 }
+
+/// Number of gates appended to the circuit by [`merkle_opening`]
+///
+/// Every one of the `DEPTH + 1` levels of the branch takes a gate for each bit
+/// of the offset and one to check their sum, three gates for each element of
+/// the level to check the position of the needle, and a permutation. The leaf
+/// is checked against the needle of the base level with an additional gate.
+pub const fn merkle_opening_gates<const DEPTH: usize>() -> usize {
+    let level = WIDTH + 3 * (WIDTH - 1) + PERMUTATION_GATES;
+
+    (DEPTH + 1) * level + 1
+}
@@ -0,0 +1,301 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! # Dusk-Poseidon Test Vectors
+//!
+//! Deterministic known-answer vectors to check implementations of this crate
+//! in other languages.
+//!
+//! The vectors are emitted as JSON by [`json`], and by the `poseidon-vectors`
+//! binary. Scalars and points are encoded as the `0x` prefixed hexadecimal
+//! string of their `to_bytes` representation. The inputs are `-(i + 1)`, for
+//! `i` in `0..`, so they exercise the full width of the encoding.
+//!
+//! The following sections are emitted:
+//!
+//! - `sponge`: [`sponge::hash`] of the first `0..=20` inputs
+//! - `truncated`: [`truncated::hash`] of the first `0..=8` inputs
+//! - `two_outputs`: [`perm_uses::two_outputs`] of the first 4 inputs
+//! - `cipher`: [`PoseidonCipher`] of two inputs, for the shared secret `k · G`
+//!   and the nonce `k`, with `k` in `1..=3`
+//! - `merkle`: roots and branches of trees of several `DEPTH`s, with the leaves
+//!   [`sponge::hash`] of `[i]`. Only emitted with the `canon` feature.
+
+use crate::cipher::PoseidonCipher;
+use crate::sponge::{self, truncated};
+use crate::{perm_uses, Error};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+#[cfg(feature = "canon")]
+pub use merkle::VectorLeaf;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_jubjub::{JubJubAffine, JubJubScalar, GENERATOR_EXTENDED};
+
+/// Indentation of a nesting level of the JSON output
+const INDENT: usize = 2;
+
+/// The `i`-th input of the vectors
+fn input(i: usize) -> BlsScalar {
+    -BlsScalar::from(i as u64 + 1)
+}
+
+fn inputs(n: usize) -> Vec<BlsScalar> {
+    (0..n).map(input).collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut s = String::from("\"0x");
+
+    bytes.iter().for_each(|b| {
+        // Writing to a `String` never fails
+        let _ = write!(s, "{:02x}", b);
+    });
+
+    s.push('"');
+    s
+}
+
+fn scalar(scalar: &BlsScalar) -> String {
+    hex(&scalar.to_bytes())
+}
+
+fn scalars(scalars: &[BlsScalar]) -> String {
+    let scalars: Vec<String> = scalars.iter().map(scalar).collect();
+
+    format!("[{}]", scalars.join(", "))
+}
+
+fn indent(level: usize) -> String {
+    " ".repeat(level * INDENT)
+}
+
+/// Format the `items` as a JSON array, one item per line, nested at `level`
+fn array(level: usize, items: &[String]) -> String {
+    if items.is_empty() {
+        return String::from("[]");
+    }
+
+    let items: Vec<String> = items
+        .iter()
+        .map(|i| format!("{}{}", indent(level + 1), i))
+        .collect();
+
+    format!("[\n{}\n{}]", items.join(",\n"), indent(level))
+}
+
+/// Format the `fields` as a JSON object, one field per line, nested at
+/// `level`
+fn object(level: usize, fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| {
+            format!("{}\"{}\": {}", indent(level + 1), name, value)
+        })
+        .collect();
+
+    format!("{{\n{}\n{}}}", fields.join(",\n"), indent(level))
+}
+
+fn sponge_vectors() -> Vec<String> {
+    (0..=20)
+        .map(|n| {
+            let input = inputs(n);
+            let output = sponge::hash(&input);
+
+            format!(
+                "{{\"input\": {}, \"output\": {}}}",
+                scalars(&input),
+                scalar(&output)
+            )
+        })
+        .collect()
+}
+
+fn truncated_vectors() -> Vec<String> {
+    (0..=8)
+        .map(|n| {
+            let input = inputs(n);
+            let output = truncated::hash(&input);
+
+            format!(
+                "{{\"input\": {}, \"output\": {}}}",
+                scalars(&input),
+                hex(&output.to_bytes())
+            )
+        })
+        .collect()
+}
+
+fn two_outputs_vectors() -> Vec<String> {
+    (0..4)
+        .map(|i| {
+            let input = input(i);
+            let output = perm_uses::two_outputs(input);
+
+            format!(
+                "{{\"input\": {}, \"output\": {}}}",
+                scalar(&input),
+                scalars(&output)
+            )
+        })
+        .collect()
+}
+
+fn cipher_vectors() -> Vec<String> {
+    (1..=3)
+        .map(|k| {
+            let secret = GENERATOR_EXTENDED * JubJubScalar::from(k);
+            let secret = JubJubAffine::from(secret);
+
+            let nonce = BlsScalar::from(k);
+            let message = inputs(PoseidonCipher::capacity());
+
+            let cipher = PoseidonCipher::encrypt(&message, &secret, &nonce);
+
+            format!(
+                concat!(
+                    "{{\"secret\": {}, \"nonce\": {}, ",
+                    "\"message\": {}, \"cipher\": {}}}"
+                ),
+                hex(&secret.to_bytes()),
+                scalar(&nonce),
+                scalars(&message),
+                scalars(cipher.cipher())
+            )
+        })
+        .collect()
+}
+
+#[cfg(feature = "canon")]
+mod merkle {
+    use super::{array, object, scalar, scalars};
+    use crate::sponge;
+    use crate::tree::{PoseidonAnnotation, PoseidonLeaf, PoseidonTree};
+    use crate::Error;
+
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use canonical_derive::Canon;
+    use dusk_bls12_381::BlsScalar;
+
+    /// Nesting level of a tree vector
+    const LEVEL: usize = 2;
+
+    /// Leaf of the `merkle` vectors, holding the hash it is committed with
+    #[derive(Debug, Default, Clone, Copy, Canon)]
+    pub struct VectorLeaf {
+        hash: BlsScalar,
+        pos: u64,
+    }
+
+    impl VectorLeaf {
+        /// Create a new leaf from its hash, to be positioned by the tree
+        pub const fn new(hash: BlsScalar) -> Self {
+            Self { hash, pos: 0 }
+        }
+    }
+
+    impl PoseidonLeaf for VectorLeaf {
+        fn poseidon_hash(&self) -> BlsScalar {
+            self.hash
+        }
+
+        fn pos(&self) -> &u64 {
+            &self.pos
+        }
+
+        fn set_pos(&mut self, pos: u64) {
+            self.pos = pos;
+        }
+    }
+
+    fn tree<const DEPTH: usize>(leaves: u64) -> Result<String, Error> {
+        let mut tree =
+            PoseidonTree::<VectorLeaf, PoseidonAnnotation, DEPTH>::new();
+
+        let leaves: Vec<BlsScalar> = (0..leaves)
+            .map(|i| sponge::hash(&[BlsScalar::from(i)]))
+            .collect();
+
+        leaves.iter().try_for_each(|hash| {
+            tree.push(VectorLeaf::new(*hash)).map(|_| ())
+        })?;
+
+        let n = leaves.len() as u64;
+        let branches = [0, n / 2, n - 1]
+            .iter()
+            .map(|&i| -> Result<String, Error> {
+                let branch = tree.branch(i)?.ok_or(Error::TreeBranchFailed)?;
+
+                let levels: Vec<String> = branch
+                    .as_ref()
+                    .iter()
+                    .map(|l| {
+                        format!(
+                            "{{\"offset\": {}, \"level\": {}}}",
+                            l.offset(),
+                            scalars(l.as_ref())
+                        )
+                    })
+                    .collect();
+
+                Ok(object(
+                    LEVEL + 2,
+                    &[
+                        ("index", format!("{}", i)),
+                        ("levels", array(LEVEL + 3, &levels)),
+                    ],
+                ))
+            })
+            .collect::<Result<Vec<String>, Error>>()?;
+
+        let leaves: Vec<String> = leaves.iter().map(scalar).collect();
+
+        Ok(object(
+            LEVEL,
+            &[
+                ("depth", format!("{}", DEPTH)),
+                ("leaves", array(LEVEL + 1, &leaves)),
+                ("root", scalar(&tree.root()?)),
+                ("branches", array(LEVEL + 1, &branches)),
+            ],
+        ))
+    }
+
+    pub(super) fn vectors() -> Result<Vec<String>, Error> {
+        Ok([tree::<2>(3)?, tree::<4>(21)?, tree::<17>(70)?].to_vec())
+    }
+}
+
+/// Emit the known-answer vectors as a JSON object
+pub fn json() -> Result<String, Error> {
+    #[allow(unused_mut)]
+    let mut sections = [
+        ("sponge", sponge_vectors()),
+        ("truncated", truncated_vectors()),
+        ("two_outputs", two_outputs_vectors()),
+        ("cipher", cipher_vectors()),
+    ]
+    .to_vec();
+
+    #[cfg(feature = "canon")]
+    sections.push(("merkle", merkle::vectors()?));
+
+    let sections: Vec<(&str, String)> = sections
+        .iter()
+        .map(|(name, vectors)| (*name, array(1, vectors)))
+        .collect();
+
+    Ok(object(0, &sections))
+}
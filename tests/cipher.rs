@@ -12,7 +12,10 @@ use dusk_jubjub::{
     GENERATOR_EXTENDED,
 };
 use dusk_plonk::error::Error as PlonkError;
-use dusk_poseidon::cipher::{self, PoseidonCipher};
+use dusk_poseidon::cipher::{
+    self, Cipher, PoseidonCipher, PoseidonVarCipher, SealedMessage,
+    StreamDecryptor, StreamEncryptor,
+};
 use dusk_poseidon::Error;
 use rand_core::{OsRng, RngCore};
 
@@ -189,7 +192,7 @@ impl<'a> Circuit for TestCipherCircuit<'a> {
             });
 
         let cipher_gadget =
-            cipher::encrypt(composer, &shared, nonce, &message_circuit);
+            cipher::encrypt::<2>(composer, &shared, nonce, &message_circuit);
 
         self.cipher
             .iter()
@@ -200,7 +203,7 @@ impl<'a> Circuit for TestCipherCircuit<'a> {
             });
 
         let message_gadget =
-            cipher::decrypt(composer, &shared, nonce, &cipher_gadget);
+            cipher::decrypt::<2>(composer, &shared, nonce, &cipher_gadget);
 
         self.message
             .iter()
@@ -222,6 +225,68 @@ impl<'a> Circuit for TestCipherCircuit<'a> {
     }
 }
 
+#[derive(Debug, Default)]
+pub struct TestGatesCircuit {
+    encrypt_gates: usize,
+    decrypt_gates: usize,
+}
+
+impl Circuit for TestGatesCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let (message, secret, nonce) = gen();
+        let cipher = PoseidonCipher::encrypt(&message, &secret, &nonce);
+
+        let secret = composer.append_point(secret);
+        let nonce = composer.append_witness(nonce);
+
+        let message: Vec<Witness> = message
+            .iter()
+            .map(|m| composer.append_witness(*m))
+            .collect();
+        let cipher: Vec<Witness> = cipher
+            .cipher()
+            .iter()
+            .map(|c| composer.append_witness(*c))
+            .collect();
+
+        let gates = composer.gates();
+        cipher::encrypt::<2>(composer, &secret, nonce, &message);
+        self.encrypt_gates = composer.gates() - gates;
+
+        let gates = composer.gates();
+        cipher::decrypt::<2>(composer, &secret, nonce, &cipher);
+        self.decrypt_gates = composer.gates() - gates;
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << 13
+    }
+}
+
+#[test]
+fn gadget_gates() -> Result<(), PlonkError> {
+    let pp = PublicParameters::setup(1 << 13, &mut OsRng)?;
+
+    let mut circuit = TestGatesCircuit::default();
+    circuit.compile(&pp)?;
+
+    assert_eq!(circuit.encrypt_gates, cipher::encrypt_gates::<2>());
+    assert_eq!(circuit.decrypt_gates, cipher::decrypt_gates::<2>());
+
+    Ok(())
+}
+
 #[test]
 fn gadget() -> Result<(), PlonkError> {
     // Generate a secret and a public key for Bob
@@ -262,3 +327,977 @@ fn gadget() -> Result<(), PlonkError> {
 
     Ok(())
 }
+
+fn gen_var(len: usize) -> Vec<BlsScalar> {
+    (0..len).map(|_| BlsScalar::random(&mut OsRng)).collect()
+}
+
+#[test]
+fn var_encrypt() -> Result<(), Error> {
+    let (_, secret, nonce) = gen();
+
+    for len in 0..11 {
+        let message = gen_var(len);
+
+        let cipher = PoseidonVarCipher::encrypt(&message, &secret, &nonce);
+        assert_eq!(cipher.cipher().len(), PoseidonVarCipher::cipher_size(len));
+        assert_eq!(cipher.message_len(), len);
+
+        let decrypt = cipher.decrypt(&secret, &nonce)?;
+        assert_eq!(message, decrypt);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn var_fixed_length() {
+    let (message, secret, nonce) = gen();
+
+    let cipher = PoseidonCipher::encrypt(&message, &secret, &nonce);
+    let var = PoseidonVarCipher::encrypt(&message, &secret, &nonce);
+
+    assert_eq!(&cipher.cipher()[..], var.cipher());
+}
+
+#[test]
+fn var_tampered_fail() {
+    let (_, secret, nonce) = gen();
+    let (_, wrong_secret, _) = gen();
+    let message = gen_var(7);
+
+    let cipher = PoseidonVarCipher::encrypt(&message, &secret, &nonce);
+    assert!(cipher.decrypt(&wrong_secret, &nonce).is_err());
+
+    let wrong_nonce = nonce + BlsScalar::one();
+    assert!(cipher.decrypt(&secret, &wrong_nonce).is_err());
+
+    let mut tampered = cipher.cipher().to_vec();
+    tampered[3] += BlsScalar::one();
+    let tampered = PoseidonVarCipher::new(tampered);
+    assert!(tampered.decrypt(&secret, &nonce).is_err());
+
+    // The length of the message is bound to the cipher
+    let mut truncated = cipher.cipher().to_vec();
+    truncated.remove(message.len() - 1);
+    let truncated = PoseidonVarCipher::new(truncated);
+    assert!(truncated.decrypt(&secret, &nonce).is_err());
+
+    let empty = PoseidonVarCipher::new(vec![]);
+    assert!(empty.decrypt(&secret, &nonce).is_err());
+}
+
+#[test]
+fn var_bytes() -> Result<(), Error> {
+    let (_, secret, nonce) = gen();
+    let message = gen_var(5);
+
+    let cipher = PoseidonVarCipher::encrypt(&message, &secret, &nonce);
+
+    let bytes = cipher.to_var_bytes();
+    assert_eq!(bytes.len(), 6 * BlsScalar::SIZE);
+
+    let restored_cipher = PoseidonVarCipher::from_slice(&bytes).unwrap();
+    assert_eq!(cipher, restored_cipher);
+
+    let decrypt = restored_cipher.decrypt(&secret, &nonce)?;
+    assert_eq!(message, decrypt);
+
+    assert!(PoseidonVarCipher::from_slice(&[]).is_err());
+    assert!(PoseidonVarCipher::from_slice(&bytes[1..]).is_err());
+    assert!(PoseidonVarCipher::from_slice(&[0xff; 32]).is_err());
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+pub struct TestVarCipherCircuit {
+    secret: JubJubAffine,
+    nonce: BlsScalar,
+    message: Vec<BlsScalar>,
+    cipher: Vec<BlsScalar>,
+    encrypt_gates: usize,
+    decrypt_gates: usize,
+}
+
+impl TestVarCipherCircuit {
+    pub fn new(
+        secret: JubJubAffine,
+        nonce: BlsScalar,
+        message: Vec<BlsScalar>,
+    ) -> Self {
+        let cipher = PoseidonVarCipher::encrypt(&message, &secret, &nonce);
+        let cipher = cipher.cipher().to_vec();
+
+        Self {
+            secret,
+            nonce,
+            message,
+            cipher,
+            ..Default::default()
+        }
+    }
+}
+
+impl Circuit for TestVarCipherCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let secret = composer.append_point(self.secret);
+        let nonce = composer.append_witness(self.nonce);
+
+        let message: Vec<Witness> = self
+            .message
+            .iter()
+            .map(|m| composer.append_witness(*m))
+            .collect();
+
+        let gates = composer.gates();
+        let cipher = cipher::encrypt_var(composer, &secret, nonce, &message);
+        self.encrypt_gates = composer.gates() - gates;
+
+        self.cipher.iter().zip(cipher.iter()).for_each(|(c, g)| {
+            let x = composer.append_witness(*c);
+            composer.assert_equal(x, *g);
+        });
+
+        let gates = composer.gates();
+        let decrypt = cipher::decrypt_var(composer, &secret, nonce, &cipher);
+        self.decrypt_gates = composer.gates() - gates;
+
+        message.iter().zip(decrypt.iter()).for_each(|(m, g)| {
+            composer.assert_equal(*m, *g);
+        });
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << 14
+    }
+}
+
+#[test]
+fn var_gadget() -> Result<(), PlonkError> {
+    let label = b"poseidon-cipher";
+    let pp = PublicParameters::setup(1 << 14, &mut OsRng)?;
+
+    for len in [1, 4, 7].iter() {
+        let (_, secret, nonce) = gen();
+
+        let mut circuit = TestVarCipherCircuit::new(
+            JubJubAffine::default(),
+            BlsScalar::zero(),
+            vec![BlsScalar::zero(); *len],
+        );
+        let (pk, vd) = circuit.compile(&pp)?;
+
+        assert_eq!(circuit.encrypt_gates, cipher::encrypt_var_gates(*len));
+        assert_eq!(circuit.decrypt_gates, cipher::decrypt_var_gates(*len));
+
+        let proof = TestVarCipherCircuit::new(secret, nonce, gen_var(*len))
+            .prove(&pp, &pk, label)?;
+
+        TestVarCipherCircuit::verify(&pp, &vd, &proof, &[], label)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct TestEmptyVarCipherCircuit;
+
+impl Circuit for TestEmptyVarCipherCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let secret = composer.append_point(JubJubAffine::default());
+        let nonce = composer.append_witness(BlsScalar::zero());
+
+        cipher::decrypt_var(composer, &secret, nonce, &[]);
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << 9
+    }
+}
+
+#[test]
+#[should_panic(expected = "The cipher must contain the tag")]
+fn var_gadget_empty() {
+    let pp = PublicParameters::setup(1 << 9, &mut OsRng)
+        .expect("Failed to setup the parameters");
+
+    TestEmptyVarCipherCircuit.compile(&pp).ok();
+}
+
+#[test]
+fn generic_encrypt() -> Result<(), Error> {
+    let (_, secret, nonce) = gen();
+
+    let message = gen_var(3);
+    let cipher = Cipher::<3>::encrypt(&message, &secret, &nonce);
+    assert_eq!(Cipher::<3>::capacity(), 3);
+    assert_eq!(cipher.cipher().len(), Cipher::<3>::cipher_size());
+    assert_eq!(message, cipher.decrypt(&secret, &nonce)?);
+
+    let var = PoseidonVarCipher::encrypt(&message, &secret, &nonce);
+    assert_eq!(&cipher.cipher()[..], var.cipher());
+
+    let message = gen_var(7);
+    let cipher = Cipher::<7>::encrypt(&message, &secret, &nonce);
+    assert_eq!(message, cipher.decrypt(&secret, &nonce)?);
+
+    let var = PoseidonVarCipher::encrypt(&message, &secret, &nonce);
+    assert_eq!(&cipher.cipher()[..], var.cipher());
+
+    // Shorter messages are padded with zeroes
+    let cipher = Cipher::<4>::encrypt(&message[..2], &secret, &nonce);
+    let decrypt = cipher.decrypt(&secret, &nonce)?;
+    assert_eq!(message[..2], decrypt[..2]);
+    assert_eq!([BlsScalar::zero(); 2], decrypt[2..]);
+
+    let wrong_nonce = nonce + BlsScalar::one();
+    assert!(cipher.decrypt(&secret, &wrong_nonce).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn generic_bytes() -> Result<(), Error> {
+    let (_, secret, nonce) = gen();
+    let message = gen_var(3);
+
+    let cipher = Cipher::<3>::encrypt(&message, &secret, &nonce);
+
+    let bytes = cipher.to_bytes();
+    assert_eq!(bytes.len(), 128);
+    assert_eq!(bytes.len(), Cipher::<3>::cipher_size_bytes());
+
+    let restored_cipher = Cipher::<3>::from_bytes(&bytes).unwrap();
+    assert_eq!(cipher, restored_cipher);
+    assert_eq!(message, restored_cipher.decrypt(&secret, &nonce)?);
+
+    assert_eq!(Cipher::<4>::SIZE, 160);
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+pub struct TestGenericCipherCircuit<const N: usize> {
+    secret: JubJubAffine,
+    nonce: BlsScalar,
+    message: Vec<BlsScalar>,
+    cipher: Vec<BlsScalar>,
+    encrypt_gates: usize,
+    decrypt_gates: usize,
+}
+
+impl TestGenericCipherCircuit<3> {
+    pub fn new(
+        secret: JubJubAffine,
+        nonce: BlsScalar,
+        message: Vec<BlsScalar>,
+    ) -> Self {
+        let cipher = Cipher::<3>::encrypt(&message, &secret, &nonce);
+        let cipher = cipher.cipher().to_vec();
+
+        Self {
+            secret,
+            nonce,
+            message,
+            cipher,
+            ..Default::default()
+        }
+    }
+}
+
+impl Circuit for TestGenericCipherCircuit<3> {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let secret = composer.append_point(self.secret);
+        let nonce = composer.append_witness(self.nonce);
+
+        let message: Vec<Witness> = self
+            .message
+            .iter()
+            .map(|m| composer.append_witness(*m))
+            .collect();
+
+        let gates = composer.gates();
+        let cipher = cipher::encrypt::<3>(composer, &secret, nonce, &message);
+        self.encrypt_gates = composer.gates() - gates;
+
+        self.cipher.iter().zip(cipher.iter()).for_each(|(c, g)| {
+            let x = composer.append_witness(*c);
+            composer.assert_equal(x, *g);
+        });
+
+        let gates = composer.gates();
+        let decrypt = cipher::decrypt::<3>(composer, &secret, nonce, &cipher);
+        self.decrypt_gates = composer.gates() - gates;
+
+        message.iter().zip(decrypt.iter()).for_each(|(m, g)| {
+            composer.assert_equal(*m, *g);
+        });
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << 13
+    }
+}
+
+#[test]
+fn generic_gadget() -> Result<(), PlonkError> {
+    let label = b"poseidon-cipher";
+    let pp = PublicParameters::setup(1 << 13, &mut OsRng)?;
+    let (_, secret, nonce) = gen();
+
+    let mut circuit = TestGenericCipherCircuit::new(
+        JubJubAffine::default(),
+        BlsScalar::zero(),
+        vec![BlsScalar::zero(); 3],
+    );
+    let (pk, vd) = circuit.compile(&pp)?;
+
+    assert_eq!(circuit.encrypt_gates, cipher::encrypt_gates::<3>());
+    assert_eq!(circuit.decrypt_gates, cipher::decrypt_gates::<3>());
+
+    let proof = TestGenericCipherCircuit::new(secret, nonce, gen_var(3))
+        .prove(&pp, &pk, label)?;
+
+    TestGenericCipherCircuit::verify(&pp, &vd, &proof, &[], label)?;
+
+    Ok(())
+}
+
+#[test]
+fn ad_encrypt() -> Result<(), Error> {
+    let (message, secret, nonce) = gen();
+
+    for len in [0, 1, 4, 5].iter() {
+        let ad = gen_var(*len);
+
+        let cipher =
+            PoseidonCipher::encrypt_with_ad(&message, &ad, &secret, &nonce);
+        let decrypt = cipher.decrypt_with_ad(&ad, &secret, &nonce)?;
+        assert_eq!(message, decrypt);
+    }
+
+    // Without associated data, the cipher is the same as the plain encryption
+    let cipher =
+        PoseidonCipher::encrypt_with_ad(&message, &[], &secret, &nonce);
+    assert_eq!(cipher, PoseidonCipher::encrypt(&message, &secret, &nonce));
+
+    Ok(())
+}
+
+#[test]
+fn ad_tampered_fail() {
+    let (message, secret, nonce) = gen();
+    let ad = gen_var(3);
+
+    let cipher =
+        PoseidonCipher::encrypt_with_ad(&message, &ad, &secret, &nonce);
+
+    let mut tampered = ad.clone();
+    tampered[1] += BlsScalar::one();
+    assert!(matches!(
+        cipher.decrypt_with_ad(&tampered, &secret, &nonce),
+        Err(Error::CipherDecryptionFailed)
+    ));
+
+    // The length of the associated data is bound to the cipher
+    let mut extended = ad.clone();
+    extended.push(BlsScalar::zero());
+    assert!(cipher.decrypt_with_ad(&extended, &secret, &nonce).is_err());
+    assert!(cipher.decrypt_with_ad(&ad[..2], &secret, &nonce).is_err());
+    assert!(cipher.decrypt(&secret, &nonce).is_err());
+
+    let mut tampered = *cipher.cipher();
+    tampered[0] += BlsScalar::one();
+    let tampered = PoseidonCipher::new(tampered);
+    assert!(tampered.decrypt_with_ad(&ad, &secret, &nonce).is_err());
+}
+
+#[derive(Debug, Default)]
+pub struct TestAdCipherCircuit {
+    secret: JubJubAffine,
+    nonce: BlsScalar,
+    ad: Vec<BlsScalar>,
+    message: [BlsScalar; PoseidonCipher::capacity()],
+    cipher: [BlsScalar; PoseidonCipher::cipher_size()],
+    encrypt_gates: usize,
+    decrypt_gates: usize,
+}
+
+impl TestAdCipherCircuit {
+    pub fn new(
+        secret: JubJubAffine,
+        nonce: BlsScalar,
+        ad: Vec<BlsScalar>,
+        message: [BlsScalar; PoseidonCipher::capacity()],
+    ) -> Self {
+        let cipher =
+            PoseidonCipher::encrypt_with_ad(&message, &ad, &secret, &nonce);
+        let cipher = *cipher.cipher();
+
+        Self {
+            secret,
+            nonce,
+            ad,
+            message,
+            cipher,
+            ..Default::default()
+        }
+    }
+}
+
+impl Circuit for TestAdCipherCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let secret = composer.append_point(self.secret);
+        let nonce = composer.append_witness(self.nonce);
+
+        let ad: Vec<Witness> = self
+            .ad
+            .iter()
+            .map(|a| composer.append_public_witness(*a))
+            .collect();
+
+        let mut message = [TurboComposer::constant_zero(); 2];
+        self.message
+            .iter()
+            .zip(message.iter_mut())
+            .for_each(|(m, v)| *v = composer.append_witness(*m));
+
+        let gates = composer.gates();
+        let cipher = cipher::encrypt_with_ad::<2>(
+            composer, &secret, nonce, &ad, &message,
+        );
+        self.encrypt_gates = composer.gates() - gates;
+
+        self.cipher.iter().zip(cipher.iter()).for_each(|(c, g)| {
+            let x = composer.append_witness(*c);
+            composer.assert_equal(x, *g);
+        });
+
+        let gates = composer.gates();
+        let decrypt = cipher::decrypt_with_ad::<2>(
+            composer, &secret, nonce, &ad, &cipher,
+        );
+        self.decrypt_gates = composer.gates() - gates;
+
+        message.iter().zip(decrypt.iter()).for_each(|(m, g)| {
+            composer.assert_equal(*m, *g);
+        });
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        self.ad.iter().map(|a| (*a).into()).collect()
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << 13
+    }
+}
+
+#[test]
+fn ad_gadget() -> Result<(), PlonkError> {
+    let label = b"poseidon-cipher";
+    let pp = PublicParameters::setup(1 << 13, &mut OsRng)?;
+
+    let (message, secret, nonce) = gen();
+    let ad = gen_var(5);
+
+    let mut circuit = TestAdCipherCircuit::new(
+        JubJubAffine::default(),
+        BlsScalar::zero(),
+        vec![BlsScalar::zero(); ad.len()],
+        [BlsScalar::zero(); PoseidonCipher::capacity()],
+    );
+    let (pk, vd) = circuit.compile(&pp)?;
+
+    assert_eq!(circuit.encrypt_gates, cipher::encrypt_ad_gates::<2>(5));
+    assert_eq!(circuit.decrypt_gates, cipher::decrypt_ad_gates::<2>(5));
+
+    let mut circuit =
+        TestAdCipherCircuit::new(secret, nonce, ad.clone(), message);
+    let proof = circuit.prove(&pp, &pk, label)?;
+    let public_inputs = circuit.public_inputs();
+
+    TestAdCipherCircuit::verify(&pp, &vd, &proof, &public_inputs, label)?;
+
+    // The proof doesn't verify with different associated data
+    let mut tampered = public_inputs;
+    tampered[0] = (ad[0] + BlsScalar::one()).into();
+    assert!(
+        TestAdCipherCircuit::verify(&pp, &vd, &proof, &tampered, label)
+            .is_err()
+    );
+
+    Ok(())
+}
+
+fn stream_encrypt(
+    message: &[BlsScalar],
+    secret: &JubJubAffine,
+    nonce: BlsScalar,
+) -> Vec<Cipher<4>> {
+    let mut encryptor = StreamEncryptor::<4>::new(secret, nonce);
+
+    let mut chunks = encryptor.encrypt_slice(message);
+    chunks.push(encryptor.finalize());
+
+    chunks
+}
+
+fn stream_decrypt(
+    chunks: &[Cipher<4>],
+    secret: &JubJubAffine,
+    nonce: BlsScalar,
+) -> Result<Vec<BlsScalar>, Error> {
+    let mut decryptor = StreamDecryptor::<4>::new(secret, nonce);
+    let mut message = vec![];
+
+    let (last, chunks) = chunks.split_last().expect("empty stream");
+    for chunk in chunks {
+        message.extend_from_slice(&decryptor.decrypt(chunk)?);
+    }
+    message.extend(decryptor.finalize(last)?);
+
+    Ok(message)
+}
+
+#[test]
+fn stream_encrypt_decrypt() -> Result<(), Error> {
+    let (_, secret, nonce) = gen();
+
+    for len in [0, 1, 3, 4, 5, 8, 21].iter() {
+        let message = gen_var(*len);
+
+        let chunks = stream_encrypt(&message, &secret, nonce);
+        assert_eq!(chunks.len(), len / 4 + 1);
+
+        let decrypt = stream_decrypt(&chunks, &secret, nonce)?;
+        assert_eq!(message, decrypt);
+    }
+
+    // Scalars can be encrypted one at a time
+    let message = gen_var(9);
+    let mut encryptor = StreamEncryptor::<4>::new(&secret, nonce);
+    let mut chunks: Vec<Cipher<4>> = message
+        .iter()
+        .filter_map(|m| encryptor.encrypt(m))
+        .collect();
+    assert_eq!(encryptor.index(), 2);
+    chunks.push(encryptor.finalize());
+
+    assert_eq!(chunks, stream_encrypt(&message, &secret, nonce));
+
+    Ok(())
+}
+
+#[test]
+fn stream_tampered_fail() {
+    let (_, secret, nonce) = gen();
+    let message = gen_var(13);
+
+    let chunks = stream_encrypt(&message, &secret, nonce);
+    assert_eq!(chunks.len(), 4);
+
+    let failed_chunk =
+        |chunks: &[Cipher<4>]| match stream_decrypt(chunks, &secret, nonce) {
+            Err(Error::StreamChunkFailed(index)) => Some(index),
+            _ => None,
+        };
+
+    // Reordered chunks
+    let mut reordered = chunks.clone();
+    reordered.swap(1, 2);
+    assert_eq!(failed_chunk(&reordered), Some(1));
+
+    // Dropped chunk
+    let mut dropped = chunks.clone();
+    dropped.remove(1);
+    assert_eq!(failed_chunk(&dropped), Some(1));
+
+    // Truncated stream
+    assert_eq!(failed_chunk(&chunks[..3]), Some(2));
+
+    // Extended stream, with the final chunk in the middle
+    let mut extended = chunks.clone();
+    extended.push(chunks[0]);
+    assert_eq!(failed_chunk(&extended), Some(3));
+
+    // Tampered chunk
+    let mut tampered = chunks.clone();
+    let mut cipher = *tampered[2].cipher();
+    cipher[1] += BlsScalar::one();
+    tampered[2] = Cipher::new(cipher);
+    assert_eq!(failed_chunk(&tampered), Some(2));
+
+    // Wrong nonce
+    let wrong_nonce = nonce + BlsScalar::one();
+    let mut decryptor = StreamDecryptor::<4>::new(&secret, wrong_nonce);
+    assert!(matches!(
+        decryptor.decrypt(&chunks[0]),
+        Err(Error::StreamChunkFailed(0))
+    ));
+    assert_eq!(decryptor.index(), 0);
+}
+
+#[test]
+fn bytes_encrypt_decrypt() -> Result<(), Error> {
+    let (_, secret, nonce) = gen();
+
+    for len in [0, 1, 30, 31, 32, 62, 100].iter() {
+        let mut bytes = vec![0u8; *len];
+        OsRng.fill_bytes(&mut bytes);
+
+        let cipher = cipher::encrypt_bytes(&bytes, &secret, &nonce);
+        assert_eq!(cipher[0], cipher::BYTES_CIPHER_VERSION);
+
+        let decrypt = cipher::decrypt_bytes(&cipher, &secret, &nonce)?;
+        assert_eq!(bytes, decrypt);
+    }
+
+    // Trailing zeroes survive the round trip
+    let bytes = [0xab, 0, 0, 0];
+    let cipher = cipher::encrypt_bytes(&bytes, &secret, &nonce);
+    let decrypt = cipher::decrypt_bytes(&cipher, &secret, &nonce)?;
+    assert_eq!(&bytes[..], &decrypt[..]);
+
+    // The length prefix, one limb and the tag
+    assert_eq!(
+        cipher.len(),
+        cipher::BYTES_CIPHER_HEADER_SIZE + 3 * BlsScalar::SIZE
+    );
+
+    Ok(())
+}
+
+#[test]
+fn bytes_malformed_fail() {
+    let (_, secret, nonce) = gen();
+    let bytes = b"memo of the note";
+
+    let cipher = cipher::encrypt_bytes(bytes, &secret, &nonce);
+
+    let decrypt =
+        |cipher: &[u8]| cipher::decrypt_bytes(cipher, &secret, &nonce);
+
+    assert!(decrypt(&[]).is_err());
+    assert!(decrypt(&cipher[..cipher.len() - 1]).is_err());
+
+    let mut version = cipher.clone();
+    version[0] += 1;
+    assert!(decrypt(&version).is_err());
+
+    let mut extended = cipher.clone();
+    extended.extend_from_slice(&[0u8; 32]);
+    assert!(decrypt(&extended).is_err());
+
+    let mut tampered = cipher.clone();
+    tampered[cipher::BYTES_CIPHER_HEADER_SIZE] ^= 1;
+    assert!(decrypt(&tampered).is_err());
+}
+
+/// Serialize a cipher of the provided scalars as in `cipher::encrypt_bytes`
+fn bytes_cipher(
+    message: &[BlsScalar],
+    secret: &JubJubAffine,
+    nonce: &BlsScalar,
+) -> Vec<u8> {
+    let cipher = PoseidonVarCipher::encrypt(message, secret, nonce);
+
+    let mut bytes = vec![cipher::BYTES_CIPHER_VERSION];
+    bytes.extend_from_slice(&(cipher.cipher().len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&cipher.to_var_bytes());
+
+    bytes
+}
+
+#[test]
+fn bytes_non_canonical_fail() -> Result<(), Error> {
+    let (_, secret, nonce) = gen();
+
+    let valid = bytes_cipher(
+        &[BlsScalar::from(2), BlsScalar::from(0x0201)],
+        &secret,
+        &nonce,
+    );
+    assert_eq!(cipher::decrypt_bytes(&valid, &secret, &nonce)?, [1, 2]);
+
+    let non_canonical = [
+        // Non-zero byte beyond the length of the last limb
+        [BlsScalar::from(1), BlsScalar::from(0x0201)],
+        // Limb above `2^248`
+        [BlsScalar::from(31), BlsScalar::from_raw([0, 0, 0, 1 << 56])],
+        // Length inconsistent with the number of limbs
+        [BlsScalar::from(32), BlsScalar::from(1)],
+        // Length above `u64`
+        [BlsScalar::from_raw([1, 1, 0, 0]), BlsScalar::from(1)],
+    ];
+
+    for message in non_canonical.iter() {
+        let cipher = bytes_cipher(message, &secret, &nonce);
+        assert!(cipher::decrypt_bytes(&cipher, &secret, &nonce).is_err());
+    }
+
+    let cipher = bytes_cipher(&[], &secret, &nonce);
+    assert!(cipher::decrypt_bytes(&cipher, &secret, &nonce).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn seal_open() -> Result<(), Error> {
+    let (message, _, _) = gen();
+
+    let secret = JubJubScalar::random(&mut OsRng);
+    let public = GENERATOR_EXTENDED * secret;
+
+    let sealed = cipher::seal(&public, &message, &mut OsRng);
+    assert_eq!(message, cipher::open(&secret, &sealed)?);
+
+    let wrong_secret = JubJubScalar::random(&mut OsRng);
+    assert!(cipher::open(&wrong_secret, &sealed).is_err());
+
+    // The ephemeral key is authenticated by the cipher
+    let ephemeral = JubJubScalar::random(&mut OsRng);
+    let ephemeral = (GENERATOR_EXTENDED * ephemeral).into();
+    let tampered =
+        SealedMessage::new(ephemeral, *sealed.nonce(), *sealed.cipher());
+    assert!(cipher::open(&secret, &tampered).is_err());
+
+    let nonce = sealed.nonce() + BlsScalar::one();
+    let tampered =
+        SealedMessage::new(*sealed.ephemeral(), nonce, *sealed.cipher());
+    assert!(cipher::open(&secret, &tampered).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn seal_small_order_ephemeral() -> Result<(), Error> {
+    let (message, _, nonce) = gen();
+
+    // With an even secret, the point of order two doesn't change the shared
+    // secret, so only the ephemeral check can reject the message
+    let secret = JubJubScalar::random(&mut OsRng);
+    let secret = secret + secret;
+    let public = GENERATOR_EXTENDED * secret;
+
+    let small_order =
+        JubJubAffine::from_raw_unchecked(BlsScalar::zero(), -BlsScalar::one());
+
+    let ephemeral_secret = JubJubScalar::random(&mut OsRng);
+    let ephemeral = GENERATOR_EXTENDED * ephemeral_secret;
+    let ephemeral: JubJubAffine =
+        (ephemeral + JubJubExtended::from(small_order)).into();
+
+    let shared_secret = dhke(&ephemeral_secret, &public);
+    let ad = [ephemeral.get_x(), ephemeral.get_y()];
+    let cipher =
+        PoseidonCipher::encrypt_with_ad(&message, &ad, &shared_secret, &nonce);
+
+    let sealed = SealedMessage::new(ephemeral, nonce, cipher);
+    assert!(matches!(
+        cipher::open(&secret, &sealed),
+        Err(Error::CipherDecryptionFailed)
+    ));
+
+    // The same message with the prime-order ephemeral opens
+    let sealed = cipher::seal_with_ephemeral(
+        &public,
+        &message,
+        &ephemeral_secret,
+        &nonce,
+    );
+    assert_eq!(message, cipher::open(&secret, &sealed)?);
+
+    Ok(())
+}
+
+#[test]
+fn seal_bytes() -> Result<(), Error> {
+    let (message, _, _) = gen();
+
+    let secret = JubJubScalar::random(&mut OsRng);
+    let public = GENERATOR_EXTENDED * secret;
+
+    let sealed = cipher::seal(&public, &message, &mut OsRng);
+
+    let bytes = sealed.to_bytes();
+    assert_eq!(bytes.len(), 32 + 32 + PoseidonCipher::cipher_size_bytes());
+
+    let restored = SealedMessage::from_bytes(&bytes).unwrap();
+    assert_eq!(sealed, restored);
+    assert_eq!(message, cipher::open(&secret, &restored)?);
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+pub struct TestSealCircuit {
+    recipient: JubJubAffine,
+    ephemeral_secret: JubJubScalar,
+    message: [BlsScalar; PoseidonCipher::capacity()],
+    sealed: Option<SealedMessage>,
+}
+
+impl TestSealCircuit {
+    pub fn new(
+        recipient: JubJubAffine,
+        ephemeral_secret: JubJubScalar,
+        nonce: BlsScalar,
+        message: [BlsScalar; PoseidonCipher::capacity()],
+    ) -> Self {
+        let sealed = cipher::seal_with_ephemeral(
+            &recipient.into(),
+            &message,
+            &ephemeral_secret,
+            &nonce,
+        );
+
+        Self {
+            recipient,
+            ephemeral_secret,
+            message,
+            sealed: Some(sealed),
+        }
+    }
+
+    fn sealed(&self) -> SealedMessage {
+        self.sealed.unwrap_or_else(|| {
+            SealedMessage::new(
+                JubJubAffine::identity(),
+                BlsScalar::zero(),
+                PoseidonCipher::default(),
+            )
+        })
+    }
+}
+
+impl Circuit for TestSealCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let sealed = self.sealed();
+
+        let recipient = composer.append_public_point(self.recipient);
+        let ephemeral_secret = composer.append_witness(self.ephemeral_secret);
+        let nonce = composer.append_public_witness(*sealed.nonce());
+
+        let mut message = [TurboComposer::constant_zero(); 2];
+        self.message
+            .iter()
+            .zip(message.iter_mut())
+            .for_each(|(m, v)| *v = composer.append_witness(*m));
+
+        let (ephemeral, cipher) = cipher::seal_gadget(
+            composer,
+            recipient,
+            ephemeral_secret,
+            nonce,
+            &message,
+        );
+
+        composer.assert_equal_public_point(ephemeral, *sealed.ephemeral());
+
+        sealed.cipher().cipher().iter().zip(cipher.iter()).for_each(
+            |(c, g)| {
+                composer.assert_equal_constant(*g, BlsScalar::zero(), Some(*c));
+            },
+        );
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        let sealed = self.sealed();
+
+        let mut pi: Vec<PublicInputValue> =
+            vec![self.recipient.into(), (*sealed.nonce()).into()];
+        pi.push((*sealed.ephemeral()).into());
+        sealed
+            .cipher()
+            .cipher()
+            .iter()
+            .for_each(|c| pi.push((*c).into()));
+
+        pi
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << 13
+    }
+}
+
+#[test]
+fn seal_gadget() -> Result<(), PlonkError> {
+    let label = b"poseidon-cipher";
+    let pp = PublicParameters::setup(1 << 13, &mut OsRng)?;
+
+    let (message, _, nonce) = gen();
+    let secret = JubJubScalar::random(&mut OsRng);
+    let recipient = (GENERATOR_EXTENDED * secret).into();
+    let ephemeral_secret = JubJubScalar::random(&mut OsRng);
+
+    let (pk, vd) = TestSealCircuit::default().compile(&pp)?;
+
+    let mut circuit =
+        TestSealCircuit::new(recipient, ephemeral_secret, nonce, message);
+    let proof = circuit.prove(&pp, &pk, label)?;
+    let public_inputs = circuit.public_inputs();
+
+    TestSealCircuit::verify(&pp, &vd, &proof, &public_inputs, label)?;
+
+    // The proof doesn't verify for another recipient
+    let other = JubJubScalar::random(&mut OsRng);
+    let other: JubJubAffine = (GENERATOR_EXTENDED * other).into();
+    let mut wrong_inputs = public_inputs;
+    wrong_inputs[0] = other.into();
+    assert!(
+        TestSealCircuit::verify(&pp, &vd, &proof, &wrong_inputs, label)
+            .is_err()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::process::Command;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_jubjub::{JubJubAffine, JubJubScalar, GENERATOR_EXTENDED};
+use dusk_poseidon::cipher::PoseidonCipher;
+use dusk_poseidon::sponge::{self, truncated};
+use rand_core::OsRng;
+
+fn hex(bytes: &[u8]) -> String {
+    let h: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    format!("0x{}", h)
+}
+
+fn cli(args: &[&str]) -> Result<String, String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_dusk-poseidon"))
+        .args(args)
+        .output()
+        .expect("Failed to run the binary");
+
+    let stdout = String::from_utf8(output.stdout).expect("Invalid stdout");
+    let stderr = String::from_utf8(output.stderr).expect("Invalid stderr");
+
+    match output.status.success() {
+        true => Ok(String::from(stdout.trim_end())),
+        false => Err(stderr),
+    }
+}
+
+fn gen() -> (Vec<BlsScalar>, Vec<String>) {
+    let scalars: Vec<BlsScalar> =
+        (0..3).map(|_| BlsScalar::random(&mut OsRng)).collect();
+    let args = scalars.iter().map(|s| hex(&s.to_bytes())).collect();
+
+    (scalars, args)
+}
+
+#[test]
+fn hash() {
+    let (input, args) = gen();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let h = hex(&sponge::hash(&input).to_bytes());
+    let t = hex(&truncated::hash(&input).to_bytes());
+
+    let mut hash = vec!["hash"];
+    hash.extend(&args);
+    assert_eq!(cli(&hash), Ok(h.clone()));
+
+    let mut truncated = vec!["hash-truncated"];
+    truncated.extend(&args);
+    assert_eq!(cli(&truncated), Ok(t));
+
+    // The `0x` prefix is optional
+    assert_eq!(cli(&["hash", args[0]]), cli(&["hash", &args[0][2..]]));
+
+    hash.insert(0, "--json");
+    assert_eq!(cli(&hash), Ok(format!("{{\"hash\": \"{}\"}}", h)));
+}
+
+#[test]
+fn cipher() {
+    let (message, args) = gen();
+    let message = &message[..PoseidonCipher::capacity()];
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let args = &args[..PoseidonCipher::capacity()];
+
+    let secret = GENERATOR_EXTENDED * JubJubScalar::random(&mut OsRng);
+    let secret = hex(&JubJubAffine::from(secret).to_bytes());
+    let nonce = hex(&BlsScalar::random(&mut OsRng).to_bytes());
+
+    let mut encrypt = vec!["encrypt", "--secret", &secret, "--nonce", &nonce];
+    encrypt.extend(args);
+    let cipher = cli(&encrypt).expect("Failed to encrypt");
+
+    let decrypt = ["decrypt", "--secret", &secret, "--nonce", &nonce, &cipher];
+    assert_eq!(cli(&decrypt), Ok(args.join("\n")));
+
+    let wrong = hex(&BlsScalar::random(&mut OsRng).to_bytes());
+    let decrypt = ["decrypt", "--secret", &secret, "--nonce", &wrong, &cipher];
+    assert!(cli(&decrypt).is_err());
+
+    let decrypt = ["--json", "decrypt", "--secret", &secret, "--nonce", &nonce];
+    let decrypt: Vec<&str> =
+        decrypt.iter().copied().chain([&*cipher]).collect();
+    let expected: Vec<String> = message
+        .iter()
+        .map(|m| format!("\"{}\"", hex(&m.to_bytes())))
+        .collect();
+    assert_eq!(
+        cli(&decrypt),
+        Ok(format!("{{\"message\": [{}]}}", expected.join(", ")))
+    );
+}
+
+#[test]
+fn invalid_input() {
+    assert!(cli(&[]).is_err());
+    assert!(cli(&["unknown"]).is_err());
+    assert!(cli(&["hash", "0x00"]).is_err());
+    assert!(cli(&["hash", &"ff".repeat(32)]).is_err());
+    assert!(cli(&["hash", &format!("+f{}", "00".repeat(31))]).is_err());
+    assert!(cli(&["encrypt", "--nonce"]).is_err());
+}
+
+#[cfg(feature = "canon")]
+mod tree {
+    use super::*;
+
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use dusk_poseidon::tree::{PoseidonAnnotation, PoseidonTree};
+    use dusk_poseidon::vectors::VectorLeaf;
+    use rand_core::RngCore;
+
+    const DEPTH: usize = 4;
+
+    /// Write the leaf file to a unique path of the temporary directory
+    fn leaf_file(lines: &[String]) -> PathBuf {
+        let name =
+            format!("dusk-poseidon-cli-leaves-{:016x}", OsRng.next_u64());
+        let path = env::temp_dir().join(name);
+
+        let file = format!("# leaves\n\n{}\n", lines.join("\n"));
+        fs::write(&path, file).expect("Failed to write the leaf file");
+
+        path
+    }
+
+    #[test]
+    fn tree() {
+        let (leaves, lines) = gen();
+
+        let mut tree =
+            PoseidonTree::<VectorLeaf, PoseidonAnnotation, DEPTH>::new();
+        leaves.iter().for_each(|hash| {
+            tree.push(VectorLeaf::new(*hash))
+                .expect("Failed to append to the tree");
+        });
+
+        let root = tree.root().expect("Failed to fetch the root");
+        let root = hex(&root.to_bytes());
+
+        let file = leaf_file(&lines);
+        let path = file.to_str().expect("Invalid path");
+
+        let depth = format!("{}", DEPTH);
+        let args = ["tree", "root", "--depth", &depth, path];
+        assert_eq!(cli(&args), Ok(root.clone()));
+
+        let args = ["tree", "branch", "--depth", &depth, "--index", "1", path];
+        let branch = cli(&args).expect("Failed to compute the branch");
+        assert_eq!(branch.lines().count(), DEPTH + 1);
+
+        let args = ["tree", "branch", "--depth", &depth, "--index", "5", path];
+        assert!(cli(&args).is_err());
+
+        let args = ["tree", "root", "--depth", "33", path];
+        assert!(cli(&args).is_err());
+
+        fs::remove_file(&file).expect("Failed to remove the leaf file");
+
+        // A tree of depth 1 can't hold more than 4 leaves
+        let (_, mut lines) = gen();
+        lines.extend(gen().1);
+
+        let file = leaf_file(&lines[..5]);
+        let path = file.to_str().expect("Invalid path");
+
+        let args = ["tree", "root", "--depth", "1", path];
+        let err = cli(&args).expect_err("The leaves should overflow the tree");
+        assert!(err.contains("at most 4^1 leaves, got 5"));
+
+        let args = ["tree", "root", "--depth", "2", path];
+        assert!(cli(&args).is_ok());
+
+        let file4 = leaf_file(&lines[..4]);
+        let path4 = file4.to_str().expect("Invalid path");
+
+        let args = ["tree", "root", "--depth", "1", path4];
+        assert!(cli(&args).is_ok());
+
+        fs::remove_file(&file).expect("Failed to remove the leaf file");
+        fs::remove_file(&file4).expect("Failed to remove the leaf file");
+    }
+}
@@ -0,0 +1,141 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_plonk::error::Error as PlonkError;
+use dusk_poseidon::commitment::{self, PoseidonCommitment};
+use dusk_poseidon::sponge;
+use rand_core::OsRng;
+
+use dusk_plonk::prelude::*;
+
+const CAPACITY: usize = 12;
+
+fn gen() -> ([BlsScalar; 3], BlsScalar) {
+    let mut values = [BlsScalar::zero(); 3];
+    values
+        .iter_mut()
+        .for_each(|v| *v = BlsScalar::random(&mut OsRng));
+
+    (values, BlsScalar::random(&mut OsRng))
+}
+
+#[test]
+fn opening() {
+    let (values, blinder) = gen();
+    let c = commitment::commit(&values, &blinder);
+
+    assert!(commitment::verify_opening(&c, &values, &blinder));
+    assert!(!commitment::verify_opening(&c, &values[..2], &blinder));
+    assert!(!commitment::verify_opening(&c, &values, &values[0]));
+
+    // The commitment is domain separated from the plain hash
+    let mut input = values.to_vec();
+    input.push(blinder);
+    assert_ne!(*c.commitment(), sponge::hash(&input));
+}
+
+#[test]
+fn bytes() -> Result<(), dusk_bytes::Error> {
+    let (values, blinder) = gen();
+    let c = commitment::commit(&values, &blinder);
+
+    let restored = PoseidonCommitment::from_bytes(&c.to_bytes())?;
+    assert_eq!(c, restored);
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+pub struct TestCommitmentCircuit {
+    values: [BlsScalar; 3],
+    blinder: BlsScalar,
+    commitment: PoseidonCommitment,
+}
+
+impl TestCommitmentCircuit {
+    pub fn new(values: [BlsScalar; 3], blinder: BlsScalar) -> Self {
+        let commitment = commitment::commit(&values, &blinder);
+
+        Self {
+            values,
+            blinder,
+            commitment,
+        }
+    }
+}
+
+impl Circuit for TestCommitmentCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let zero = TurboComposer::constant_zero();
+
+        let mut values = [zero; 3];
+        self.values
+            .iter()
+            .zip(values.iter_mut())
+            .for_each(|(v, w)| {
+                *w = composer.append_witness(*v);
+            });
+
+        let blinder = composer.append_witness(self.blinder);
+
+        // Private commitment
+        let c = composer.append_witness(*self.commitment.commitment());
+        commitment::opening_gadget(composer, c, &values, blinder);
+
+        // Public commitment
+        commitment::public_opening_gadget(
+            composer,
+            &self.commitment,
+            &values,
+            blinder,
+        );
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![(*self.commitment.commitment()).into()]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn gadget() -> Result<(), PlonkError> {
+    let label = b"commitment-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    let (pk, vd) = TestCommitmentCircuit::default().compile(&pp)?;
+
+    let (values, blinder) = gen();
+    let c = commitment::commit(&values, &blinder);
+
+    let proof =
+        TestCommitmentCircuit::new(values, blinder).prove(&pp, &pk, label)?;
+
+    let pi = vec![(*c.commitment()).into()];
+    TestCommitmentCircuit::verify(&pp, &vd, &proof, &pi, label)?;
+
+    // The proof is not valid for a different commitment
+    let (values, blinder) = gen();
+    let c = commitment::commit(&values, &blinder);
+
+    let pi = vec![(*c.commitment()).into()];
+    assert!(
+        TestCommitmentCircuit::verify(&pp, &vd, &proof, &pi, label).is_err()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::{
+    dhke, JubJubAffine, JubJubExtended, JubJubScalar, GENERATOR_EXTENDED,
+};
+use dusk_plonk::error::Error as PlonkError;
+use dusk_poseidon::cipher::{self, PoseidonCipher};
+use dusk_poseidon::kdf::{self, Kdf};
+use dusk_poseidon::Error;
+use rand_core::OsRng;
+
+use dusk_plonk::prelude::*;
+
+fn gen() -> (JubJubAffine, [BlsScalar; 2]) {
+    let secret = JubJubScalar::random(&mut OsRng);
+    let secret = JubJubAffine::from(GENERATOR_EXTENDED * secret);
+
+    let context =
+        [BlsScalar::random(&mut OsRng), BlsScalar::random(&mut OsRng)];
+
+    (secret, context)
+}
+
+#[test]
+fn derive() {
+    let (secret, context) = gen();
+    let (other, _) = gen();
+
+    let kdf = Kdf::new(&secret, &context);
+
+    assert_eq!(
+        kdf.derive(kdf::MAC, 3),
+        kdf::derive(&secret, &context, kdf::MAC, 3)
+    );
+
+    assert_ne!(kdf.derive(kdf::MAC, 0), kdf.derive(kdf::NONCE, 0));
+    assert_ne!(kdf.derive(kdf::MAC, 0), kdf.derive(kdf::MAC, 1));
+    assert_ne!(
+        kdf.derive(kdf::MAC, 0),
+        kdf::derive(&secret, &context[..1], kdf::MAC, 0)
+    );
+    assert_ne!(
+        kdf.derive(kdf::MAC, 0),
+        kdf::derive(&other, &context, kdf::MAC, 0)
+    );
+}
+
+#[test]
+fn encrypt_with_key() -> Result<(), Error> {
+    let (secret, context) = gen();
+    let key = kdf::cipher_key(&secret, &context);
+
+    let message = [BlsScalar::random(&mut OsRng); PoseidonCipher::capacity()];
+    let nonce = BlsScalar::random(&mut OsRng);
+
+    let cipher = PoseidonCipher::encrypt_with_key(&message, &key, &nonce);
+    let decrypt = cipher.decrypt_with_key(&key, &nonce)?;
+
+    assert_eq!(message, decrypt);
+
+    // The derived key is not the raw shared secret
+    assert!(cipher.decrypt(&secret, &nonce).is_err());
+
+    // The raw shared secret is the same as the key of its coordinates
+    let key = [secret.get_x(), secret.get_y()];
+    assert_eq!(
+        PoseidonCipher::encrypt(&message, &secret, &nonce),
+        PoseidonCipher::encrypt_with_key(&message, &key, &nonce)
+    );
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+pub struct TestKdfCircuit {
+    secret: JubJubScalar,
+    public: JubJubExtended,
+    context: [BlsScalar; 2],
+    nonce: BlsScalar,
+    message: [BlsScalar; PoseidonCipher::capacity()],
+    cipher: [BlsScalar; PoseidonCipher::cipher_size()],
+    mac_key: BlsScalar,
+}
+
+impl Circuit for TestKdfCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let zero = TurboComposer::constant_zero();
+        let nonce = composer.append_witness(self.nonce);
+
+        let secret = composer.append_witness(self.secret);
+        let public = composer.append_point(self.public);
+
+        let shared = composer.component_mul_point(secret, public);
+
+        let context = [
+            composer.append_witness(self.context[0]),
+            composer.append_witness(self.context[1]),
+        ];
+
+        let kdf = kdf::KdfGadget::new(composer, &shared, &context);
+
+        let mac_key = kdf.derive(composer, kdf::MAC, 0);
+        let x = composer.append_witness(self.mac_key);
+        composer.assert_equal(x, mac_key);
+
+        let key = kdf.cipher_key(composer);
+
+        let mut message = [zero; PoseidonCipher::capacity()];
+        self.message
+            .iter()
+            .zip(message.iter_mut())
+            .for_each(|(m, v)| {
+                *v = composer.append_witness(*m);
+            });
+
+        let cipher =
+            cipher::encrypt_with_key::<2>(composer, &key, nonce, &message);
+
+        self.cipher.iter().zip(cipher.iter()).for_each(|(c, g)| {
+            let x = composer.append_witness(*c);
+            composer.assert_equal(x, *g);
+        });
+
+        let decrypted =
+            cipher::decrypt_with_key::<2>(composer, &key, nonce, &cipher);
+
+        message.iter().zip(decrypted.iter()).for_each(|(m, d)| {
+            composer.assert_equal(*m, *d);
+        });
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << 14
+    }
+}
+
+#[test]
+fn gadget() -> Result<(), PlonkError> {
+    let bob_secret = JubJubScalar::random(&mut OsRng);
+
+    let alice_secret = JubJubScalar::random(&mut OsRng);
+    let alice_public = GENERATOR_EXTENDED * alice_secret;
+
+    let shared_secret = dhke(&bob_secret, &alice_public);
+
+    let (_, context) = gen();
+    let key = kdf::cipher_key(&shared_secret, &context);
+    let mac_key = kdf::derive(&shared_secret, &context, kdf::MAC, 0);
+
+    let message = [BlsScalar::random(&mut OsRng); PoseidonCipher::capacity()];
+    let nonce = BlsScalar::random(&mut OsRng);
+    let cipher = PoseidonCipher::encrypt_with_key(&message, &key, &nonce);
+
+    let label = b"poseidon-kdf";
+    let pp = PublicParameters::setup(1 << 14, &mut OsRng)?;
+    let (pk, vd) = TestKdfCircuit::default().compile(&pp)?;
+
+    let proof = TestKdfCircuit {
+        secret: bob_secret,
+        public: alice_public,
+        context,
+        nonce,
+        message,
+        cipher: *cipher.cipher(),
+        mac_key,
+    }
+    .prove(&pp, &pk, label)?;
+
+    TestKdfCircuit::verify(&pp, &vd, &proof, &[], label)
+}
@@ -0,0 +1,105 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_plonk::error::Error as PlonkError;
+use dusk_poseidon::{mac, sponge};
+use rand_core::OsRng;
+
+use dusk_plonk::prelude::*;
+
+const CAPACITY: usize = 11;
+
+fn gen() -> (BlsScalar, [BlsScalar; 3]) {
+    let mut message = [BlsScalar::zero(); 3];
+    message
+        .iter_mut()
+        .for_each(|m| *m = BlsScalar::random(&mut OsRng));
+
+    (BlsScalar::random(&mut OsRng), message)
+}
+
+#[test]
+fn verify() {
+    let (key, message) = gen();
+    let (other, _) = gen();
+
+    let tag = mac::mac(&key, &message);
+
+    assert!(mac::verify(&key, &message, &tag));
+    assert!(!mac::verify(&other, &message, &tag));
+    assert!(!mac::verify(&key, &message[..2], &tag));
+    assert!(!mac::verify(&key, &message, &(tag + BlsScalar::one())));
+
+    // The tag is domain separated from the plain keyed hash
+    let mut input = vec![key];
+    input.extend_from_slice(&message);
+    assert_ne!(tag, sponge::hash(&input));
+}
+
+#[derive(Debug, Default)]
+pub struct TestMacCircuit {
+    key: BlsScalar,
+    message: [BlsScalar; 3],
+    tag: BlsScalar,
+}
+
+impl Circuit for TestMacCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let zero = TurboComposer::constant_zero();
+        let key = composer.append_witness(self.key);
+
+        let mut message = [zero; 3];
+        self.message
+            .iter()
+            .zip(message.iter_mut())
+            .for_each(|(m, w)| {
+                *w = composer.append_witness(*m);
+            });
+
+        let tag = composer.append_public_witness(self.tag);
+
+        mac::verify_gadget(composer, key, &message, tag);
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![self.tag.into()]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn gadget() -> Result<(), PlonkError> {
+    let label = b"mac-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    let (pk, vd) = TestMacCircuit::default().compile(&pp)?;
+
+    let (key, message) = gen();
+    let tag = mac::mac(&key, &message);
+
+    let proof = TestMacCircuit { key, message, tag }.prove(&pp, &pk, label)?;
+
+    TestMacCircuit::verify(&pp, &vd, &proof, &[tag.into()], label)?;
+
+    // The proof is not valid for a different tag
+    let tag = tag + BlsScalar::one();
+    assert!(
+        TestMacCircuit::verify(&pp, &vd, &proof, &[tag.into()], label).is_err()
+    );
+
+    Ok(())
+}
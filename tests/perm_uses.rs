@@ -0,0 +1,178 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::error::Error as PlonkError;
+use dusk_poseidon::{perm_uses, sponge};
+use rand_core::OsRng;
+
+use dusk_plonk::prelude::*;
+
+const CAPACITY: usize = 13;
+
+#[derive(Debug)]
+pub struct TestXofCircuit {
+    input: Vec<BlsScalar>,
+    output: Vec<BlsScalar>,
+}
+
+impl TestXofCircuit {
+    pub fn new(input: Vec<BlsScalar>, outputs: usize) -> Self {
+        let mut output = vec![BlsScalar::zero(); outputs];
+        perm_uses::xof(&input, &mut output);
+
+        Self { input, output }
+    }
+}
+
+impl Circuit for TestXofCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let zero = TurboComposer::constant_zero();
+
+        let i: Vec<Witness> = self
+            .input
+            .iter()
+            .map(|i| composer.append_witness(*i))
+            .collect();
+
+        let mut o = vec![zero; self.output.len()];
+        perm_uses::xof_gadget(composer, &i, &mut o);
+
+        self.output.iter().zip(o.iter()).for_each(|(x, o)| {
+            let x = composer.append_witness(*x);
+            composer.assert_equal(x, *o);
+        });
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn xof_gadget() -> Result<(), PlonkError> {
+    let label = b"xof-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    for (i, o) in [(0, 1), (1, 2), (3, 4), (4, 5), (6, 9)].iter() {
+        let (pk, vd) = TestXofCircuit::new(vec![BlsScalar::zero(); *i], *o)
+            .compile(&pp)?;
+
+        let input = (0..*i).map(|_| BlsScalar::random(&mut OsRng)).collect();
+        let proof = TestXofCircuit::new(input, *o).prove(&pp, &pk, label)?;
+
+        TestXofCircuit::verify(&pp, &vd, &proof, &[], label)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+pub struct TestFixedHashCircuit {
+    input: [BlsScalar; 4],
+    output: [BlsScalar; 4],
+    gates: [usize; 4],
+}
+
+impl TestFixedHashCircuit {
+    pub fn new(input: [BlsScalar; 4]) -> Self {
+        let [a, b, c, d] = input;
+
+        let output = [
+            perm_uses::hash1(&[a]),
+            perm_uses::hash2(&[a, b]),
+            perm_uses::hash3(&[a, b, c]),
+            perm_uses::hash4(&[a, b, c, d]),
+        ];
+
+        Self {
+            input,
+            output,
+            gates: [0; 4],
+        }
+    }
+}
+
+impl Circuit for TestFixedHashCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let a = composer.append_witness(self.input[0]);
+        let b = composer.append_witness(self.input[1]);
+        let c = composer.append_witness(self.input[2]);
+        let d = composer.append_witness(self.input[3]);
+
+        let mut o = [TurboComposer::constant_zero(); 4];
+
+        let gates = composer.gates();
+        o[0] = perm_uses::hash1_gadget(composer, &[a]);
+        self.gates[0] = composer.gates() - gates;
+
+        let gates = composer.gates();
+        o[1] = perm_uses::hash2_gadget(composer, &[a, b]);
+        self.gates[1] = composer.gates() - gates;
+
+        let gates = composer.gates();
+        o[2] = perm_uses::hash3_gadget(composer, &[a, b, c]);
+        self.gates[2] = composer.gates() - gates;
+
+        let gates = composer.gates();
+        o[3] = perm_uses::hash4_gadget(composer, &[a, b, c, d]);
+        self.gates[3] = composer.gates() - gates;
+
+        self.output.iter().zip(o.iter()).for_each(|(x, o)| {
+            let x = composer.append_witness(*x);
+            composer.assert_equal(x, *o);
+        });
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn fixed_hash_gadget() -> Result<(), PlonkError> {
+    let label = b"fixed-hash-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    let mut circuit = TestFixedHashCircuit::default();
+    let (pk, vd) = circuit.compile(&pp)?;
+
+    // One gate for the capacity constant and a single permutation
+    assert_eq!(perm_uses::hash_fixed_gates(), 1 + sponge::PERMUTATION_GATES);
+    circuit.gates.iter().for_each(|gates| {
+        assert_eq!(*gates, perm_uses::hash_fixed_gates());
+    });
+
+    let mut input = [BlsScalar::zero(); 4];
+    input
+        .iter_mut()
+        .for_each(|i| *i = BlsScalar::random(&mut OsRng));
+
+    let proof = TestFixedHashCircuit::new(input).prove(&pp, &pk, label)?;
+
+    TestFixedHashCircuit::verify(&pp, &vd, &proof, &[], label)
+}
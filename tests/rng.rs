@@ -0,0 +1,132 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_plonk::error::Error as PlonkError;
+use dusk_poseidon::perm_uses;
+use dusk_poseidon::rng::{self, PoseidonRng};
+use rand_core::{OsRng, RngCore};
+
+use dusk_plonk::prelude::*;
+
+const CAPACITY: usize = 12;
+const OUTPUTS: usize = 6;
+
+fn gen() -> [BlsScalar; 3] {
+    let mut seed = [BlsScalar::zero(); 3];
+    seed.iter_mut()
+        .for_each(|s| *s = BlsScalar::random(&mut OsRng));
+
+    seed
+}
+
+#[test]
+fn stream() {
+    let seed = gen();
+
+    let mut expected = [BlsScalar::zero(); 10];
+    perm_uses::xof(&seed, &mut expected);
+
+    let mut rng = PoseidonRng::new(&seed);
+    expected
+        .iter()
+        .for_each(|e| assert_eq!(e, &rng.next_scalar()));
+
+    let mut rng = PoseidonRng::new(&seed[..2]);
+    assert_ne!(expected[0], rng.next_scalar());
+
+    let mut expected = [BlsScalar::zero(); 1];
+    perm_uses::xof(&[], &mut expected);
+    assert_eq!(expected[0], PoseidonRng::new(&[]).next_scalar());
+}
+
+#[test]
+fn bytes() {
+    let seed = gen();
+
+    let mut a = PoseidonRng::new(&seed);
+    let mut b = PoseidonRng::new(&seed);
+
+    let mut bytes = [0u8; 40];
+    a.fill_bytes(&mut bytes);
+
+    assert_eq!(&bytes[..16], &b.next_scalar().to_bytes()[..16]);
+    assert_eq!(&bytes[16..32], &b.next_scalar().to_bytes()[..16]);
+    assert_eq!(&bytes[32..], &b.next_scalar().to_bytes()[..8]);
+
+    let mut a = PoseidonRng::new(&seed);
+    let mut b = PoseidonRng::new(&seed);
+
+    assert_eq!(a.next_u64(), b.next_u64());
+    assert_eq!(a.next_u32(), b.next_u32());
+    assert_eq!(BlsScalar::random(&mut a), BlsScalar::random(&mut b));
+}
+
+#[derive(Debug, Default)]
+pub struct TestRngCircuit {
+    seed: [BlsScalar; 3],
+    output: [BlsScalar; OUTPUTS],
+}
+
+impl TestRngCircuit {
+    pub fn new(seed: [BlsScalar; 3]) -> Self {
+        let mut rng = PoseidonRng::new(&seed);
+
+        let mut output = [BlsScalar::zero(); OUTPUTS];
+        output.iter_mut().for_each(|o| *o = rng.next_scalar());
+
+        Self { seed, output }
+    }
+}
+
+impl Circuit for TestRngCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let zero = TurboComposer::constant_zero();
+
+        let mut seed = [zero; 3];
+        self.seed.iter().zip(seed.iter_mut()).for_each(|(s, w)| {
+            *w = composer.append_witness(*s);
+        });
+
+        let mut output = [zero; OUTPUTS];
+        rng::gadget(composer, &seed, &mut output);
+
+        self.output.iter().zip(output.iter()).for_each(|(o, w)| {
+            composer.assert_equal_constant(*w, BlsScalar::zero(), Some(*o));
+        });
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        self.output.iter().map(|o| (*o).into()).collect()
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn gadget() -> Result<(), PlonkError> {
+    let label = b"rng-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    let (pk, vd) = TestRngCircuit::default().compile(&pp)?;
+
+    let mut circuit = TestRngCircuit::new(gen());
+    let pi = circuit.public_inputs();
+
+    let proof = circuit.prove(&pp, &pk, label)?;
+
+    TestRngCircuit::verify(&pp, &vd, &proof, &pi, label)
+}
@@ -5,10 +5,11 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use dusk_bls12_381::BlsScalar;
-use dusk_bytes::ParseHexStr;
+use dusk_bytes::{ParseHexStr, Serializable};
 use dusk_plonk::error::Error as PlonkError;
 use dusk_poseidon::sponge;
-use rand_core::OsRng;
+use dusk_poseidon::sponge::truncated::Truncation;
+use rand_core::{OsRng, RngCore};
 
 use dusk_plonk::prelude::*;
 
@@ -247,3 +248,962 @@ fn truncated_sponge() -> Result<(), PlonkError> {
 
     Ok(())
 }
+
+#[test]
+fn sponge_incremental() {
+    let input: Vec<BlsScalar> = TEST_INPUTS
+        .iter()
+        .map(|input| BlsScalar::from_hex_str(input).unwrap())
+        .collect();
+
+    for w in 0..13 {
+        let mut s = sponge::Sponge::new();
+        input[..w].iter().for_each(|i| s.absorb(i));
+
+        assert_eq!(sponge::hash(&input[..w]), s.finalize());
+    }
+
+    // A cloned midstate should behave as a fresh sponge with the same prefix
+    let mut prefix = sponge::Sponge::new();
+    prefix.absorb_slice(&input[..5]);
+
+    for w in 5..13 {
+        let mut s = prefix.clone();
+        s.absorb_slice(&input[5..w]);
+
+        assert_eq!(sponge::hash(&input[..w]), s.finalize());
+    }
+}
+
+#[derive(Debug)]
+pub struct TestIncrementalSpongeCircuit {
+    input: Vec<BlsScalar>,
+    output: BlsScalar,
+}
+
+impl TestIncrementalSpongeCircuit {
+    pub fn new(input: Vec<BlsScalar>, output: BlsScalar) -> Self {
+        Self { input, output }
+    }
+}
+
+impl Circuit for TestIncrementalSpongeCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let i: Vec<Witness> = self
+            .input
+            .iter()
+            .map(|i| composer.append_witness(*i))
+            .collect();
+
+        let (head, tail) = i.split_at(i.len() / 2);
+
+        let mut s = sponge::SpongeGadget::new();
+        s.absorb_slice(composer, head);
+        tail.iter().for_each(|t| s.absorb(composer, *t));
+
+        let computed_o = s.finalize(composer);
+
+        let o = composer.append_witness(self.output);
+        composer.assert_equal(o, computed_o);
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn sponge_incremental_gadget() -> Result<(), PlonkError> {
+    let label = b"sponge-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    for w in [3, 4, 8, 9].iter() {
+        let w = *w;
+
+        let (pk, vd) = TestIncrementalSpongeCircuit::new(
+            vec![BlsScalar::zero(); w],
+            BlsScalar::zero(),
+        )
+        .compile(&pp)?;
+
+        let (i, o) = poseidon_sponge_params(w);
+        let proof =
+            TestIncrementalSpongeCircuit::new(i, o).prove(&pp, &pk, label)?;
+
+        TestIncrementalSpongeCircuit::verify(&pp, &vd, &proof, &[], label)?;
+    }
+
+    Ok(())
+}
+
+const VAR_LEN_MAX: usize = 9;
+
+#[derive(Debug)]
+pub struct TestVarLenSpongeCircuit {
+    input: [BlsScalar; VAR_LEN_MAX],
+    len: usize,
+    output: BlsScalar,
+}
+
+impl TestVarLenSpongeCircuit {
+    pub fn new(input: &[BlsScalar], output: BlsScalar) -> Self {
+        let len = input.len();
+        let mut padded = [BlsScalar::random(&mut OsRng); VAR_LEN_MAX];
+        padded[..len].copy_from_slice(input);
+
+        Self {
+            input: padded,
+            len,
+            output,
+        }
+    }
+}
+
+impl Circuit for TestVarLenSpongeCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let zero = TurboComposer::constant_zero();
+
+        let mut i = [zero; VAR_LEN_MAX];
+        self.input.iter().zip(i.iter_mut()).for_each(|(s, w)| {
+            *w = composer.append_witness(*s);
+        });
+
+        let len = composer.append_witness(BlsScalar::from(self.len as u64));
+
+        let computed_o = sponge::gadget_var_len(composer, &i, len);
+
+        let o = composer.append_witness(self.output);
+        composer.assert_equal(o, computed_o);
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn sponge_var_len_gadget() -> Result<(), PlonkError> {
+    let label = b"sponge-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    // A single circuit description is used for every length
+    let (pk, vd) =
+        TestVarLenSpongeCircuit::new(&[], BlsScalar::zero()).compile(&pp)?;
+
+    for w in 0..=VAR_LEN_MAX {
+        let (i, o) = poseidon_sponge_params(w);
+        let proof =
+            TestVarLenSpongeCircuit::new(&i, o).prove(&pp, &pk, label)?;
+
+        TestVarLenSpongeCircuit::verify(&pp, &vd, &proof, &[], label)?;
+    }
+
+    // The length is bound to the message
+    let (i, _) = poseidon_sponge_params(5);
+    let o = sponge::hash(&i[..4]);
+    let proof = TestVarLenSpongeCircuit::new(&i, o).prove(&pp, &pk, label)?;
+
+    assert!(
+        TestVarLenSpongeCircuit::verify(&pp, &vd, &proof, &[], label).is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn safe_sponge_pattern() {
+    use sponge::safe::{Call, SafeSponge};
+
+    let input: Vec<BlsScalar> = TEST_INPUTS
+        .iter()
+        .map(|input| BlsScalar::from_hex_str(input).unwrap())
+        .collect();
+
+    let pattern = [Call::Absorb(5), Call::Squeeze(1), Call::Squeeze(2)];
+    let mut output = [BlsScalar::zero(); 3];
+
+    let mut s = SafeSponge::start(&pattern).expect("Failed to start");
+    s.absorb(&input[..5]).expect("Failed to absorb");
+    s.squeeze(&mut output[..1]).expect("Failed to squeeze");
+    s.squeeze(&mut output[1..]).expect("Failed to squeeze");
+    s.finish().expect("Failed to finish");
+
+    // Consecutive calls are aggregated in the tag
+    let aggregated = [Call::Absorb(5), Call::Squeeze(3)];
+    let mut a_output = [BlsScalar::zero(); 3];
+
+    let mut s = SafeSponge::start(&aggregated).expect("Failed to start");
+    s.absorb(&input[..5]).expect("Failed to absorb");
+    s.squeeze(&mut a_output).expect("Failed to squeeze");
+    s.finish().expect("Failed to finish");
+
+    assert_eq!(output, a_output);
+
+    // A different pattern will produce a different output
+    let other = [Call::Absorb(5), Call::Squeeze(4)];
+    let mut o_output = [BlsScalar::zero(); 4];
+
+    let mut s = SafeSponge::start(&other).expect("Failed to start");
+    s.absorb(&input[..5]).expect("Failed to absorb");
+    s.squeeze(&mut o_output).expect("Failed to squeeze");
+    s.finish().expect("Failed to finish");
+
+    assert_ne!(output[0], o_output[0]);
+
+    // Calls out of the pattern are rejected
+    let mut s = SafeSponge::start(&pattern).expect("Failed to start");
+    assert!(s.absorb(&input[..4]).is_err());
+    assert!(s.absorb(&input[..5]).is_err());
+
+    let mut s = SafeSponge::start(&pattern).expect("Failed to start");
+    assert!(s.squeeze(&mut output).is_err());
+
+    let mut s = SafeSponge::start(&pattern).expect("Failed to start");
+    s.absorb(&input[..5]).expect("Failed to absorb");
+    assert!(s.finish().is_err());
+}
+
+#[test]
+fn safe_sponge_invalid_pattern() {
+    use sponge::safe::{tag, Call, SafeSponge};
+
+    // Zero-length calls are rejected
+    assert!(tag(&[Call::Absorb(0)]).is_err());
+    assert!(tag(&[Call::Absorb(1), Call::Squeeze(0)]).is_err());
+    assert!(tag(&[Call::Absorb(1), Call::Absorb(0)]).is_err());
+    assert!(SafeSponge::start(&[Call::Squeeze(0)]).is_err());
+
+    // Lengths of at least `2^31` would collide with the absorb flag
+    let n = 5;
+    assert!(tag(&[Call::Squeeze(0x8000_0000 + n)]).is_err());
+    assert!(tag(&[Call::Absorb(0x8000_0000)]).is_err());
+    assert!(tag(&[Call::Absorb(n)]).is_ok());
+    assert!(tag(&[Call::Absorb(0x7fff_ffff)]).is_ok());
+
+    // Aggregated calls can't reach `2^31`, nor overflow
+    let half = [Call::Absorb(0x4000_0000), Call::Absorb(0x4000_0000)];
+    assert!(tag(&half).is_err());
+
+    let overflow = [Call::Squeeze(0x7fff_ffff), Call::Squeeze(0x7fff_ffff)];
+    assert!(tag(&overflow).is_err());
+
+    let overflow = [
+        Call::Absorb(0x7fff_ffff),
+        Call::Absorb(0x7fff_ffff),
+        Call::Absorb(2),
+    ];
+    assert!(tag(&overflow).is_err());
+}
+
+#[derive(Debug)]
+pub struct TestSafeSpongeCircuit {
+    input: Vec<BlsScalar>,
+    output: Vec<BlsScalar>,
+}
+
+const SAFE_PATTERN: [sponge::safe::Call; 4] = [
+    sponge::safe::Call::Absorb(6),
+    sponge::safe::Call::Squeeze(1),
+    sponge::safe::Call::Absorb(2),
+    sponge::safe::Call::Squeeze(5),
+];
+
+impl TestSafeSpongeCircuit {
+    pub fn new(input: Vec<BlsScalar>) -> Self {
+        let mut output = vec![BlsScalar::zero(); 6];
+
+        let mut s = sponge::safe::SafeSponge::start(&SAFE_PATTERN)
+            .expect("Failed to start");
+        s.absorb(&input[..6]).expect("Failed to absorb");
+        s.squeeze(&mut output[..1]).expect("Failed to squeeze");
+        s.absorb(&input[6..]).expect("Failed to absorb");
+        s.squeeze(&mut output[1..]).expect("Failed to squeeze");
+        s.finish().expect("Failed to finish");
+
+        Self { input, output }
+    }
+}
+
+impl Circuit for TestSafeSpongeCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let zero = TurboComposer::constant_zero();
+
+        let i: Vec<Witness> = self
+            .input
+            .iter()
+            .map(|i| composer.append_witness(*i))
+            .collect();
+
+        let mut o = vec![zero; self.output.len()];
+
+        let mut s =
+            sponge::safe::SafeSpongeGadget::start(composer, &SAFE_PATTERN)
+                .expect("Failed to start");
+        s.absorb(composer, &i[..6]).expect("Failed to absorb");
+        s.squeeze(composer, &mut o[..1]).expect("Failed to squeeze");
+        s.absorb(composer, &i[6..]).expect("Failed to absorb");
+        s.squeeze(composer, &mut o[1..]).expect("Failed to squeeze");
+        s.finish().expect("Failed to finish");
+
+        self.output.iter().zip(o.iter()).for_each(|(x, o)| {
+            let x = composer.append_witness(*x);
+            composer.assert_equal(x, *o);
+        });
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn safe_sponge_gadget() -> Result<(), PlonkError> {
+    let label = b"safe-sponge-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    let (pk, vd) =
+        TestSafeSpongeCircuit::new(vec![BlsScalar::zero(); 8]).compile(&pp)?;
+
+    let (i, _) = poseidon_sponge_params(8);
+    let proof = TestSafeSpongeCircuit::new(i).prove(&pp, &pk, label)?;
+
+    TestSafeSpongeCircuit::verify(&pp, &vd, &proof, &[], label)
+}
+
+#[test]
+fn sponge_domain() {
+    use sponge::domain;
+
+    let input: Vec<BlsScalar> = TEST_INPUTS
+        .iter()
+        .map(|input| BlsScalar::from_hex_str(input).unwrap())
+        .collect();
+
+    for w in 0..9 {
+        let m = &input[..w];
+
+        assert_eq!(
+            sponge::hash(m),
+            sponge::hash_with_domain(domain::DEFAULT, m)
+        );
+
+        let leaf = sponge::hash_with_domain(domain::MERKLE_LEAF, m);
+        let node = sponge::hash_with_domain(domain::MERKLE_NODE, m);
+
+        assert_ne!(leaf, node);
+        assert_ne!(leaf, sponge::hash(m));
+
+        let mut s = sponge::Sponge::with_domain(domain::MERKLE_LEAF);
+        s.absorb_slice(m);
+
+        assert_eq!(leaf, s.finalize());
+    }
+}
+
+#[derive(Debug)]
+pub struct TestDomainSpongeCircuit {
+    domain: u64,
+    input: Vec<BlsScalar>,
+    output: BlsScalar,
+}
+
+impl TestDomainSpongeCircuit {
+    pub fn new(domain: u64, input: Vec<BlsScalar>) -> Self {
+        let output = sponge::hash_with_domain(domain, &input);
+
+        Self {
+            domain,
+            input,
+            output,
+        }
+    }
+}
+
+impl Circuit for TestDomainSpongeCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let i: Vec<Witness> = self
+            .input
+            .iter()
+            .map(|i| composer.append_witness(*i))
+            .collect();
+
+        let computed_o = sponge::gadget_with_domain(composer, self.domain, &i);
+
+        let mut s = sponge::SpongeGadget::with_domain(composer, self.domain);
+        s.absorb_slice(composer, &i);
+        let incremental_o = s.finalize(composer);
+
+        let o = composer.append_witness(self.output);
+        composer.assert_equal(o, computed_o);
+        composer.assert_equal(o, incremental_o);
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn sponge_domain_gadget() -> Result<(), PlonkError> {
+    use sponge::domain;
+
+    let label = b"sponge-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    for d in [domain::DEFAULT, domain::COMMITMENT].iter() {
+        for w in [0, 3, 4].iter() {
+            let (pk, vd) =
+                TestDomainSpongeCircuit::new(*d, vec![BlsScalar::zero(); *w])
+                    .compile(&pp)?;
+
+            let (i, _) = poseidon_sponge_params(*w);
+            let proof =
+                TestDomainSpongeCircuit::new(*d, i).prove(&pp, &pk, label)?;
+
+            TestDomainSpongeCircuit::verify(&pp, &vd, &proof, &[], label)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn sponge_hash_many() {
+    use sponge::domain;
+
+    let input: Vec<BlsScalar> = TEST_INPUTS
+        .iter()
+        .map(|input| BlsScalar::from_hex_str(input).unwrap())
+        .collect();
+
+    let messages: Vec<&[BlsScalar]> =
+        (0..input.len()).map(|i| &input[..i]).collect();
+
+    let serial: Vec<BlsScalar> =
+        messages.iter().map(|m| sponge::hash(m)).collect();
+    assert_eq!(serial, sponge::hash_many(&messages));
+
+    let serial: Vec<BlsScalar> = messages
+        .iter()
+        .map(|m| sponge::hash_with_domain(domain::MERKLE_LEAF, m))
+        .collect();
+    assert_eq!(serial, sponge::hash_leaves(&messages));
+}
+
+#[test]
+fn sponge_hash_bytes() {
+    let bytes: Vec<u8> = (0..70u8).collect();
+
+    let mut chunk = [0u8; 32];
+    chunk[..31].copy_from_slice(&bytes[..31]);
+    let p0 = BlsScalar::from_bytes(&chunk).unwrap();
+
+    let mut chunk = [0u8; 32];
+    chunk[..31].copy_from_slice(&bytes[31..62]);
+    let p1 = BlsScalar::from_bytes(&chunk).unwrap();
+
+    let mut chunk = [0u8; 32];
+    chunk[..8].copy_from_slice(&bytes[62..]);
+    let p2 = BlsScalar::from_bytes(&chunk).unwrap();
+
+    assert_eq!(
+        sponge::hash(&[BlsScalar::from(70u64), p0, p1, p2]),
+        sponge::hash_bytes(&bytes)
+    );
+
+    // Trailing zeroes are not ambiguous
+    assert_ne!(sponge::hash_bytes(&[]), sponge::hash_bytes(&[0]));
+    assert_ne!(sponge::hash_bytes(&[1]), sponge::hash_bytes(&[1, 0]));
+}
+
+#[derive(Debug)]
+pub struct TestBytesSpongeCircuit {
+    input: Vec<BlsScalar>,
+    output: BlsScalar,
+}
+
+impl TestBytesSpongeCircuit {
+    pub fn new(input: Vec<u8>) -> Self {
+        let output = sponge::hash_bytes(&input);
+        let input = input.iter().map(|i| BlsScalar::from(*i as u64)).collect();
+
+        Self { input, output }
+    }
+}
+
+impl Circuit for TestBytesSpongeCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let i: Vec<Witness> = self
+            .input
+            .iter()
+            .map(|i| composer.append_witness(*i))
+            .collect();
+
+        let computed_o = sponge::gadget_bytes(composer, &i);
+
+        let o = composer.append_witness(self.output);
+        composer.assert_equal(o, computed_o);
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn sponge_bytes_gadget() -> Result<(), PlonkError> {
+    let label = b"sponge-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    for w in [0, 5, 31, 40].iter() {
+        let (pk, vd) =
+            TestBytesSpongeCircuit::new(vec![0u8; *w]).compile(&pp)?;
+
+        let mut i = vec![0u8; *w];
+        OsRng.fill_bytes(&mut i);
+
+        let proof = TestBytesSpongeCircuit::new(i).prove(&pp, &pk, label)?;
+
+        TestBytesSpongeCircuit::verify(&pp, &vd, &proof, &[], label)?;
+    }
+
+    // The byte witnesses `[257, 0]` pack to the same scalar as `[1, 1]`, and
+    // only the range constraint of the bytes rejects them
+    let (pk, vd) = TestBytesSpongeCircuit::new(vec![0u8; 2]).compile(&pp)?;
+
+    let mut circuit = TestBytesSpongeCircuit::new(vec![1, 1]);
+    circuit.input = vec![BlsScalar::from(257u64), BlsScalar::zero()];
+
+    assert!(circuit
+        .prove(&pp, &pk, label)
+        .and_then(|proof| {
+            TestBytesSpongeCircuit::verify(&pp, &vd, &proof, &[], label)
+        })
+        .is_err());
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct TestTruncatedBitsCircuit<T> {
+    input: Vec<BlsScalar>,
+    output: T,
+    gates: usize,
+}
+
+impl<T: Truncation> TestTruncatedBitsCircuit<T> {
+    pub fn new(input: Vec<BlsScalar>) -> Self {
+        let output = sponge::truncated::hash_bits(&input);
+
+        Self {
+            input,
+            output,
+            gates: 0,
+        }
+    }
+}
+
+impl<T: Truncation> Circuit for TestTruncatedBitsCircuit<T> {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let i: Vec<Witness> = self
+            .input
+            .iter()
+            .map(|i| composer.append_witness(*i))
+            .collect();
+
+        let gates = composer.gates();
+        let computed_o = sponge::truncated::gadget_bits::<T>(composer, &i);
+        self.gates = composer.gates() - gates;
+
+        let o = composer.append_witness(self.output.to_scalar());
+        composer.assert_equal(o, computed_o);
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+fn truncated_bits<T: Truncation>() -> Result<(), PlonkError> {
+    let label = b"truncated-sponge-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    let mut circuit =
+        TestTruncatedBitsCircuit::<T>::new(vec![BlsScalar::zero(); 5]);
+    let (pk, vd) = circuit.compile(&pp)?;
+
+    assert_eq!(circuit.gates, sponge::truncated::gadget_bits_gates::<T>(5));
+
+    let (i, _) = poseidon_sponge_params(5);
+    let h = sponge::hash(&i);
+
+    let mut circuit = TestTruncatedBitsCircuit::<T>::new(i);
+
+    // The truncated value is composed of the low bits of the hash
+    let t = circuit.output.to_scalar();
+    let mut h = h.to_bytes();
+    let t = t.to_bytes();
+    (T::BITS..256).for_each(|b| h[b / 8] &= !(1 << (b % 8)));
+    assert_eq!(h, t);
+
+    let proof = circuit.prove(&pp, &pk, label)?;
+
+    TestTruncatedBitsCircuit::<T>::verify(&pp, &vd, &proof, &[], label)
+}
+
+#[test]
+fn truncated_sponge_bits() -> Result<(), PlonkError> {
+    truncated_bits::<u64>()?;
+    truncated_bits::<u128>()?;
+    truncated_bits::<JubJubScalar>()?;
+
+    let input: Vec<BlsScalar> = TEST_INPUTS
+        .iter()
+        .map(|input| BlsScalar::from_hex_str(input).unwrap())
+        .collect();
+
+    assert_eq!(
+        sponge::truncated::hash(&input),
+        sponge::truncated::hash_bits::<JubJubScalar>(&input)
+    );
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct TestPointSpongeCircuit {
+    input: Vec<BlsScalar>,
+    output: JubJubAffine,
+}
+
+const POINT_CAPACITY: usize = 14;
+
+impl TestPointSpongeCircuit {
+    pub fn new(input: Vec<BlsScalar>) -> Self {
+        let output = sponge::point::hash(&input);
+
+        Self { input, output }
+    }
+}
+
+impl Circuit for TestPointSpongeCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let i: Vec<Witness> = self
+            .input
+            .iter()
+            .map(|i| composer.append_witness(*i))
+            .collect();
+
+        let computed_o = sponge::point::gadget(composer, &i);
+
+        composer.assert_equal_public_point(computed_o, self.output);
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![self.output.into()]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << POINT_CAPACITY
+    }
+}
+
+#[test]
+fn point_sponge() -> Result<(), PlonkError> {
+    let input: Vec<BlsScalar> = TEST_INPUTS
+        .iter()
+        .map(|input| BlsScalar::from_hex_str(input).unwrap())
+        .collect();
+
+    let label = b"point-sponge-tester";
+    let pp = PublicParameters::setup(1 << POINT_CAPACITY, &mut OsRng)?;
+
+    let (pk, vd) =
+        TestPointSpongeCircuit::new(vec![BlsScalar::zero(); 3]).compile(&pp)?;
+
+    for w in 0..8 {
+        let i = input[w..w + 3].to_vec();
+        let o = sponge::point::hash(&i);
+
+        assert!(bool::from(o.is_prime_order()));
+        assert_ne!(o, JubJubAffine::identity());
+
+        let proof = TestPointSpongeCircuit::new(i).prove(&pp, &pk, label)?;
+
+        TestPointSpongeCircuit::verify(&pp, &vd, &proof, &[o.into()], label)?;
+    }
+
+    Ok(())
+}
+
+const MIXED_CAPACITY: usize = 13;
+
+#[derive(Debug)]
+pub struct TestMixedSpongeCircuit {
+    input: Vec<BlsScalar>,
+    constant: Vec<bool>,
+    output: BlsScalar,
+    gates: usize,
+    mixed_gates: usize,
+}
+
+impl TestMixedSpongeCircuit {
+    pub fn new(input: Vec<BlsScalar>, constant: Vec<bool>) -> Self {
+        let output = sponge::hash(&input);
+
+        Self {
+            input,
+            constant,
+            output,
+            gates: 0,
+            mixed_gates: 0,
+        }
+    }
+}
+
+impl Circuit for TestMixedSpongeCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let o = composer.append_witness(self.output);
+
+        let gates = composer.gates();
+        let i: Vec<Witness> = self
+            .input
+            .iter()
+            .zip(self.constant.iter())
+            .map(|(i, c)| match c {
+                true => composer.append_constant(*i),
+                false => composer.append_witness(*i),
+            })
+            .collect();
+        let computed_o = sponge::gadget(composer, &i);
+        self.gates = composer.gates() - gates;
+
+        let gates = composer.gates();
+        let i: Vec<sponge::GadgetInput> = self
+            .input
+            .iter()
+            .zip(self.constant.iter())
+            .map(|(i, c)| match c {
+                true => sponge::GadgetInput::Constant(*i),
+                false => composer.append_witness(*i).into(),
+            })
+            .collect();
+        let mixed_o = sponge::gadget_mixed(composer, &i);
+        self.mixed_gates = composer.gates() - gates;
+
+        composer.assert_equal(o, computed_o);
+        composer.assert_equal(o, mixed_o);
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << MIXED_CAPACITY
+    }
+}
+
+#[test]
+fn sponge_mixed_gadget() -> Result<(), PlonkError> {
+    let label = b"sponge-tester";
+    let pp = PublicParameters::setup(1 << MIXED_CAPACITY, &mut OsRng)?;
+
+    let prefix = [true; 4];
+    let shapes: [&[bool]; 6] = [
+        &[false, false, false],
+        &[true, false, true],
+        &[true, true, false, false, false],
+        &[true, true, true, true, false, false],
+        &[false, false, false, false, true, true, true, true],
+        &prefix,
+    ];
+
+    for constant in shapes.iter() {
+        let (i, _) = poseidon_sponge_params(constant.len());
+
+        // The constants define the circuit, so they must be known when it is
+        // compiled
+        let compile_i = i
+            .iter()
+            .zip(constant.iter())
+            .map(|(i, c)| if *c { *i } else { BlsScalar::zero() })
+            .collect();
+
+        let mut circuit =
+            TestMixedSpongeCircuit::new(compile_i, constant.to_vec());
+        let (pk, vd) = circuit.compile(&pp)?;
+
+        if constant.iter().all(|c| !c) {
+            assert!(circuit.mixed_gates <= circuit.gates);
+        } else {
+            assert!(circuit.mixed_gates < circuit.gates);
+        }
+
+        let proof = TestMixedSpongeCircuit::new(i, constant.to_vec())
+            .prove(&pp, &pk, label)?;
+
+        TestMixedSpongeCircuit::verify(&pp, &vd, &proof, &[], label)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn sponge_mixed_gadget_gates() -> Result<(), PlonkError> {
+    let pp = PublicParameters::setup(1 << MIXED_CAPACITY, &mut OsRng)?;
+
+    let gates = |constant: &[bool]| -> Result<(usize, usize), PlonkError> {
+        let (i, _) = poseidon_sponge_params(constant.len());
+        let mut circuit = TestMixedSpongeCircuit::new(i, constant.to_vec());
+        circuit.compile(&pp)?;
+
+        Ok((circuit.gates, circuit.mixed_gates))
+    };
+
+    // A constant message is hashed natively, and only its output is appended
+    assert_eq!(gates(&[true; 9])?.1, 1);
+
+    // A constant prefix that fills a chunk saves a full permutation
+    let permutation = gates(&[false; 4])?.0 - gates(&[false; 3])?.0;
+    let (gates, mixed) = gates(&[true, true, true, true, false, false, false])?;
+    assert!(mixed + permutation <= gates);
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+pub struct TestGatesCircuit {
+    n: usize,
+    gates: usize,
+    truncated_gates: usize,
+}
+
+impl Circuit for TestGatesCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let i: Vec<Witness> = (0..self.n)
+            .map(|_| composer.append_witness(BlsScalar::random(&mut OsRng)))
+            .collect();
+
+        let gates = composer.gates();
+        sponge::gadget(composer, &i);
+        self.gates = composer.gates() - gates;
+
+        let gates = composer.gates();
+        sponge::truncated::gadget(composer, &i);
+        self.truncated_gates = composer.gates() - gates;
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << MIXED_CAPACITY
+    }
+}
+
+#[test]
+fn sponge_gadget_gates() -> Result<(), PlonkError> {
+    let pp = PublicParameters::setup(1 << MIXED_CAPACITY, &mut OsRng)?;
+
+    for n in 0..10 {
+        let mut circuit = TestGatesCircuit {
+            n,
+            ..Default::default()
+        };
+        circuit.compile(&pp)?;
+
+        assert_eq!(circuit.gates, sponge::gadget_gates(n));
+        assert_eq!(circuit.truncated_gates, sponge::truncated::gadget_gates(n));
+    }
+
+    Ok(())
+}
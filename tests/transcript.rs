@@ -0,0 +1,150 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::{JubJubAffine, JubJubScalar, GENERATOR_EXTENDED};
+use dusk_plonk::error::Error as PlonkError;
+use dusk_poseidon::transcript::{Transcript, TranscriptGadget};
+use rand_core::OsRng;
+
+use dusk_plonk::prelude::*;
+
+const CAPACITY: usize = 13;
+
+fn gen() -> ([BlsScalar; 5], JubJubAffine) {
+    let mut scalars = [BlsScalar::zero(); 5];
+    scalars
+        .iter_mut()
+        .for_each(|s| *s = BlsScalar::random(&mut OsRng));
+
+    let point = GENERATOR_EXTENDED * JubJubScalar::random(&mut OsRng);
+
+    (scalars, point.into())
+}
+
+fn challenges(
+    scalars: &[BlsScalar],
+    point: &JubJubAffine,
+) -> (BlsScalar, JubJubScalar, BlsScalar) {
+    let mut transcript = Transcript::new(b"transcript-tester");
+
+    transcript.append_point(b"point", point);
+    scalars
+        .iter()
+        .for_each(|s| transcript.append_scalar(b"scalar", s));
+
+    let c = transcript.challenge_scalar(b"c");
+    let e = transcript.challenge_jubjub(b"e");
+
+    transcript.append_scalar(b"response", &c);
+    let f = transcript.challenge_scalar(b"f");
+
+    (c, e, f)
+}
+
+#[test]
+fn transcript() {
+    let (scalars, point) = gen();
+
+    let (c, e, f) = challenges(&scalars, &point);
+    assert_eq!((c, e, f), challenges(&scalars, &point));
+
+    assert_ne!(c, f);
+    assert_ne!(c, challenges(&scalars[1..], &point).0);
+
+    // The labels are bound to the challenges
+    let mut transcript = Transcript::new(b"transcript-tester");
+    transcript.append_point(b"other", &point);
+    scalars
+        .iter()
+        .for_each(|s| transcript.append_scalar(b"scalar", s));
+
+    assert_ne!(c, transcript.clone().challenge_scalar(b"c"));
+    assert_ne!(c, transcript.challenge_scalar(b"d"));
+}
+
+#[derive(Debug, Default)]
+pub struct TestTranscriptCircuit {
+    scalars: [BlsScalar; 5],
+    point: JubJubAffine,
+    c: BlsScalar,
+    e: JubJubScalar,
+    f: BlsScalar,
+}
+
+impl TestTranscriptCircuit {
+    pub fn new(scalars: [BlsScalar; 5], point: JubJubAffine) -> Self {
+        let (c, e, f) = challenges(&scalars, &point);
+
+        Self {
+            scalars,
+            point,
+            c,
+            e,
+            f,
+        }
+    }
+}
+
+impl Circuit for TestTranscriptCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        let mut transcript =
+            TranscriptGadget::new(composer, b"transcript-tester");
+
+        let point = composer.append_point(self.point);
+        transcript.append_point(composer, b"point", &point);
+
+        self.scalars.iter().for_each(|s| {
+            let s = composer.append_witness(*s);
+            transcript.append_scalar(composer, b"scalar", s);
+        });
+
+        let c = transcript.challenge_scalar(composer, b"c");
+        let e = transcript.challenge_jubjub(composer, b"e");
+
+        transcript.append_scalar(composer, b"response", c);
+        let f = transcript.challenge_scalar(composer, b"f");
+
+        composer.assert_equal_constant(c, BlsScalar::zero(), Some(self.c));
+        composer.assert_equal_constant(
+            e,
+            BlsScalar::zero(),
+            Some(self.e.into()),
+        );
+        composer.assert_equal_constant(f, BlsScalar::zero(), Some(self.f));
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![self.c.into(), BlsScalar::from(self.e).into(), self.f.into()]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn gadget() -> Result<(), PlonkError> {
+    let label = b"transcript-tester";
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng)?;
+
+    let (pk, vd) = TestTranscriptCircuit::default().compile(&pp)?;
+
+    let (scalars, point) = gen();
+    let mut circuit = TestTranscriptCircuit::new(scalars, point);
+    let pi = circuit.public_inputs();
+
+    let proof = circuit.prove(&pp, &pk, label)?;
+
+    TestTranscriptCircuit::verify(&pp, &vd, &proof, &pi, label)
+}
@@ -158,3 +158,49 @@ impl Circuit for MerkleOpeningCircuit {
             .expect("Proof verification failed");
     }
 
+struct MerkleGatesCircuit {
+    branch: PoseidonBranch<DEPTH>,
+    gates: usize,
+}
+
+impl Circuit for MerkleGatesCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut TurboComposer,
+    ) -> Result<(), PlonkError> {
+        use std::ops::Deref;
+
+        let leaf: BlsScalar = *self.branch.deref();
+        let leaf = composer.append_witness(leaf);
+
+        let gates = composer.gates();
+        tree::merkle_opening::<DEPTH>(composer, &self.branch, leaf);
+        self.gates = composer.gates() - gates;
+
+        Ok(())
+    }
+
+    fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![]
+    }
+
+    fn padded_gates(&self) -> usize {
+        1 << CAPACITY
+    }
+}
+
+#[test]
+fn tree_merkle_opening_gates() {
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut OsRng).unwrap();
+
+    let mut tree = Tree::default();
+    let MerkleOpeningCircuit { branch } =
+        MerkleOpeningCircuit::random(&mut OsRng, &mut tree);
+
+    let mut circuit = MerkleGatesCircuit { branch, gates: 0 };
+    circuit.compile(&pp).expect("Failed to compile circuit");
+
+    assert_eq!(circuit.gates, tree::merkle_opening_gates::<DEPTH>());
+}
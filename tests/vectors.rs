@@ -0,0 +1,77 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_poseidon::{vectors, Error};
+
+/// Vectors checked in with `cargo run --bin poseidon-vectors`
+const VECTORS: &str = include_str!("vectors.json");
+
+/// Section of the vectors that is only emitted with the `canon` feature
+const MERKLE: &str = "merkle";
+
+/// Split the vectors into their named sections
+///
+/// The separators between sections are dropped, so a section compares equal
+/// regardless of its position in the object.
+fn sections(json: &str) -> Vec<(&str, Vec<&str>)> {
+    let mut sections: Vec<(&str, Vec<&str>)> = vec![];
+
+    json.lines()
+        .filter(|line| *line != "{" && *line != "}")
+        .for_each(|line| {
+            match line.strip_prefix("  \"").and_then(|l| l.split_once('"')) {
+                Some((name, _)) => sections.push((name, vec![])),
+                None => {
+                    if let Some((_, lines)) = sections.last_mut() {
+                        lines.push(line.trim_end_matches(','));
+                    }
+                }
+            }
+        });
+
+    sections
+}
+
+#[test]
+fn vectors_match() -> Result<(), Error> {
+    let json = vectors::json()?;
+
+    let emitted = sections(&json);
+    let expected: Vec<(&str, Vec<&str>)> = sections(VECTORS)
+        .into_iter()
+        .filter(|(name, _)| cfg!(feature = "canon") || *name != MERKLE)
+        .collect();
+
+    let names = |s: &[(&str, Vec<&str>)]| -> Vec<String> {
+        s.iter().map(|(name, _)| String::from(*name)).collect()
+    };
+    assert_eq!(names(&expected), names(&emitted));
+
+    expected.iter().zip(emitted.iter()).for_each(
+        |((name, expected), (_, emitted))| {
+            expected.iter().zip(emitted.iter()).enumerate().for_each(
+                |(i, (expected, line))| {
+                    assert_eq!(
+                        expected,
+                        line,
+                        "Vector mismatch in '{}' at line {}",
+                        name,
+                        i + 1
+                    );
+                },
+            );
+
+            assert_eq!(expected.len(), emitted.len(), "Section '{}'", name);
+        },
+    );
+
+    // With every section emitted, the vectors match byte for byte
+    if cfg!(feature = "canon") {
+        assert_eq!(VECTORS, json);
+    }
+
+    Ok(())
+}